@@ -2,78 +2,131 @@
 //! Page: <https://adventofcode.com/2022/day/1>
 //! Input: <https://adventofcode.com/2022/day/1/input>
 
+use shared_lib::{
+    diagnostic::{Diagnostic, ParseResult},
+    parse,
+    solution::{Solution, SolutionError, SolutionResult},
+};
+
 type Integer = u32;
 
 /// Calculate the sum of each calorie cluster and find the largest one
-pub fn find_max_calorie_cluster(input: &str) -> Option<Integer> {
-    input.split("\n\n").map(calculate_cluster_sum).max()
+pub fn find_max_calorie_cluster(input: &str) -> ParseResult<Integer> {
+    parse_input(input)?
+        .into_iter()
+        .max()
+        .ok_or_else(|| Diagnostic::new("input has no calorie clusters"))
 }
 
 /// Calculate the sum of each calorie cluster and return the sum of the top n from those
-pub fn sum_n_max_calorie_clusters(input: &str, n: usize) -> Integer {
-    let mut sums = input
-        .split("\n\n")
-        .map(calculate_cluster_sum)
-        .collect::<Vec<Integer>>();
+pub fn sum_n_max_calorie_clusters(input: &str, n: usize) -> ParseResult<Integer> {
+    let mut sums = parse_input(input)?;
     sums.sort_unstable_by(|a, b| b.cmp(a));
-    sums.iter().take(n).sum()
+    Ok(sums.iter().take(n).sum())
+}
+
+/// Sum of every calorie cluster in the input
+fn parse_input(input: &str) -> ParseResult<Vec<Integer>> {
+    parse::blocks(input)
+        .map(|cluster| calculate_cluster_sum(input, cluster))
+        .collect()
 }
 
 /// Take a line separated list of integers and calculate the sum of the entire cluster
 ///
 /// # Arguments
 ///
-/// * `input` - Line separated list of integers
+/// * `input` - Whole puzzle input, for pointing at invalid values
+/// * `cluster` - Line separated list of integers, which is a slice of `input`
 ///
 /// # Returns
 ///
 /// Sum of each line in the cluster
 ///
-pub fn calculate_cluster_sum(input: &str) -> Integer {
-    input
-        .split("\n")
-        .filter_map(|value| -> Option<Integer> { value.trim().parse().ok() })
+pub fn calculate_cluster_sum(input: &str, cluster: &str) -> ParseResult<Integer> {
+    cluster
+        .lines()
+        .map(|value| parse::number::<Integer>(input, value.trim()))
         .sum()
 }
 
+/// Solution for the Calorie Counting puzzle
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    fn parse(&self, input: &str) -> Option<Result<(), SolutionError>> {
+        Some(parse_input(input).map(drop).map_err(Into::into))
+    }
+
+    fn part1(&self, input: &str) -> SolutionResult {
+        Ok(Box::new(find_max_calorie_cluster(input)?))
+    }
+
+    fn part2(&self, input: &str) -> SolutionResult {
+        Ok(Box::new(sum_n_max_calorie_clusters(input, 3)?))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use shared_lib::answers::assert_answer;
 
     const BASIC_EXAMPLE: &str =
         "1000 \n2000 \n3000 \n\n4000 \n\n5000 \n6000 \n\n7000 \n8000 \n9000 \n\n10000 \n";
 
-    const INPUT: &str = include_str!("../input");
+    fn input() -> Option<String> {
+        shared_lib::input::personal(env!("CARGO_MANIFEST_DIR"))
+    }
 
     #[test]
-    fn find_max_calorie_cluster_should_be_24000_in_basic_example() {
-        let result = find_max_calorie_cluster(BASIC_EXAMPLE);
+    fn find_max_calorie_cluster_should_be_24000_in_basic_example() -> ParseResult<()> {
+        let result = find_max_calorie_cluster(BASIC_EXAMPLE)?;
+
+        assert_eq!(result, 24000);
 
-        assert_eq!(result, Some(24000));
+        Ok(())
     }
 
     #[test]
-    fn find_3_max_calorie_clusters_sum_should_be_45000_in_basic_example() {
-        let result = sum_n_max_calorie_clusters(BASIC_EXAMPLE, 3);
+    fn find_3_max_calorie_clusters_sum_should_be_45000_in_basic_example() -> ParseResult<()> {
+        let result = sum_n_max_calorie_clusters(BASIC_EXAMPLE, 3)?;
 
         assert_eq!(result, 45000);
+
+        Ok(())
     }
 
     #[test]
     fn should_find_max_calorie_cluster_input() -> anyhow::Result<()> {
-        let result = find_max_calorie_cluster(INPUT);
+        let Some(input) = input() else {
+            return Ok(());
+        };
+
+        let result = find_max_calorie_cluster(&input)?;
 
-        assert_eq!(result, Some(72602));
+        assert_answer(1, 1, result);
 
         Ok(())
     }
 
     #[test]
     fn should_find_3_max_calorie_clusters_sum_using_input() -> anyhow::Result<()> {
-        let result = sum_n_max_calorie_clusters(INPUT, 3);
+        let Some(input) = input() else {
+            return Ok(());
+        };
 
-        assert_eq!(result, 207410);
+        let result = sum_n_max_calorie_clusters(&input, 3)?;
+
+        assert_answer(1, 2, result);
 
         Ok(())
     }
+
+    #[test]
+    fn invalid_calories_should_point_at_value() {
+        let err = find_max_calorie_cluster("1000\n2000\n\n30x0\n").unwrap_err();
+
+        assert_eq!(err.position(), Some((4, 1)));
+    }
 }
@@ -1,5 +1,10 @@
 use std::cmp::{Eq, Ord, Ordering, PartialEq, PartialOrd};
 
+use shared_lib::{
+    diagnostic::{Diagnostic, ParseResult},
+    solution::{Solution, SolutionResult},
+};
+
 #[derive(PartialEq, Eq, Clone)]
 pub enum Weapon {
     Rock,
@@ -75,61 +80,94 @@ impl Ord for Weapon {
 /// * A for Rock
 /// * B for Paper
 /// * C for Scissors
-fn decrypt_left(col: &str) -> Weapon {
-    match col {
+fn decrypt_left(col: &str) -> ParseResult<Weapon> {
+    let value = match col {
         "A" => Weapon::Rock,
         "B" => Weapon::Paper,
         "C" => Weapon::Scissors,
-        _ => unreachable!(),
-    }
+        _ => {
+            return Err(Diagnostic::at(
+                col,
+                col,
+                format!("unknown column value `{col}`, expected `A`, `B` or `C`"),
+            ))
+        }
+    };
+
+    Ok(value)
 }
 
 /// Decrypt right column (part 1)
 /// * X for Rock
 /// * Y for Paper
 /// * Z for Scissors
-fn decrypt_right(col: &str) -> Weapon {
-    match col {
+fn decrypt_right(col: &str) -> ParseResult<Weapon> {
+    let value = match col {
         "X" => Weapon::Rock,
         "Y" => Weapon::Paper,
         "Z" => Weapon::Scissors,
-        _ => unreachable!(),
-    }
+        _ => {
+            return Err(Diagnostic::at(
+                col,
+                col,
+                format!("unknown column value `{col}`, expected `X`, `Y` or `Z`"),
+            ))
+        }
+    };
+
+    Ok(value)
 }
 
 /// Decrypt right column (part 2)
 /// * X for Less
 /// * Y for Equal
 /// * Z for Greater
-fn decrypt_right_ord(col: &str) -> Ordering {
-    match col {
+fn decrypt_right_ord(col: &str) -> ParseResult<Ordering> {
+    let value = match col {
         "X" => Ordering::Less,
         "Y" => Ordering::Equal,
         "Z" => Ordering::Greater,
-        _ => unreachable!(),
-    }
+        _ => {
+            return Err(Diagnostic::at(
+                col,
+                col,
+                format!("unknown column value `{col}`, expected `X`, `Y` or `Z`"),
+            ))
+        }
+    };
+
+    Ok(value)
 }
 
-fn split_line_parts(line: &str) -> (&str, &str) {
+fn split_line_parts<'a>(input: &str, line: &'a str) -> ParseResult<(&'a str, &'a str)> {
     let mut parts = line.split(" ");
 
-    parts.next().zip(parts.next()).expect("expected two values")
+    parts
+        .next()
+        .zip(parts.next())
+        .ok_or_else(|| Diagnostic::at(input, line, "expected two values separated by a space"))
 }
 
-fn parse_line(line: &str) -> (Weapon, Weapon) {
-    let (opponent, player) = split_line_parts(line);
+fn parse_line(input: &str, line: &str) -> ParseResult<(Weapon, Weapon)> {
+    let (opponent, player) = split_line_parts(input, line)?;
 
-    (decrypt_left(opponent), decrypt_right(player))
+    Ok((
+        decrypt_left(opponent).map_err(|err| err.within(input, opponent))?,
+        decrypt_right(player).map_err(|err| err.within(input, player))?,
+    ))
 }
 
 /// altered line parser after we are told that the 2nd column is actually
 /// whether you should win, lose, or draw
 ///
 /// see: [`decrypt_right_ord`]
-fn parse_line_altered(line: &str) -> (Weapon, Weapon) {
-    let (opponent, player) = split_line_parts(line);
+fn parse_line_altered(input: &str, line: &str) -> ParseResult<(Weapon, Weapon)> {
+    let (opponent, player) = split_line_parts(input, line)?;
 
-    let (opponent, player) = (decrypt_left(opponent), decrypt_right_ord(player));
+    let (opponent, player) = (
+        decrypt_left(opponent).map_err(|err| err.within(input, opponent))?,
+        decrypt_right_ord(player).map_err(|err| err.within(input, player))?,
+    );
 
     let player = match player {
         Ordering::Equal => opponent.get_equal(),
@@ -137,7 +175,7 @@ fn parse_line_altered(line: &str) -> (Weapon, Weapon) {
         Ordering::Greater => opponent.get_greater(),
     };
 
-    (opponent, player)
+    Ok((opponent, player))
 }
 
 fn calculate_round_score((opponent, player): (Weapon, Weapon)) -> u16 {
@@ -150,57 +188,98 @@ fn calculate_round_score((opponent, player): (Weapon, Weapon)) -> u16 {
     player.value() + player_score
 }
 
-pub fn calculate_score(input: &str) -> u16 {
+pub fn calculate_score(input: &str) -> ParseResult<u16> {
     input
         .trim()
         .split("\n")
-        .map(parse_line)
-        .map(calculate_round_score)
+        .map(|line| parse_line(input, line).map(calculate_round_score))
         .sum()
 }
 
-pub fn calculate_score_part2(input: &str) -> u16 {
+pub fn calculate_score_part2(input: &str) -> ParseResult<u16> {
     input
         .trim()
         .split("\n")
-        .map(parse_line_altered)
-        .map(calculate_round_score)
+        .map(|line| parse_line_altered(input, line).map(calculate_round_score))
         .sum()
 }
 
+/// Solution for the Rock Paper Scissors puzzle
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    fn part1(&self, input: &str) -> SolutionResult {
+        Ok(Box::new(calculate_score(input)?))
+    }
+
+    fn part2(&self, input: &str) -> SolutionResult {
+        Ok(Box::new(calculate_score_part2(input)?))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use shared_lib::answers::assert_answer;
 
     const BASIC_EXAMPLE: &str = "A Y\nB X\nC Z";
 
-    const INPUT: &str = include_str!("../input");
+    fn input() -> Option<String> {
+        shared_lib::input::personal(env!("CARGO_MANIFEST_DIR"))
+    }
 
     #[test]
-    fn basic_example_should_total_to_15() {
-        let result = calculate_score(BASIC_EXAMPLE);
+    fn basic_example_should_total_to_15() -> ParseResult<()> {
+        let result = calculate_score(BASIC_EXAMPLE)?;
 
         assert_eq!(result, 15);
+
+        Ok(())
     }
 
     #[test]
-    fn puzzle_input_should_equal_answer() {
-        let result = calculate_score(INPUT);
+    fn puzzle_input_should_equal_answer() -> ParseResult<()> {
+        let Some(input) = input() else {
+            return Ok(());
+        };
+
+        let result = calculate_score(&input)?;
 
-        assert_eq!(result, 10404);
+        assert_answer(2, 1, result);
+
+        Ok(())
     }
 
     #[test]
-    fn part2_basic_example_should_total_to_12() {
-        let result = calculate_score_part2(BASIC_EXAMPLE);
+    fn part2_basic_example_should_total_to_12() -> ParseResult<()> {
+        let result = calculate_score_part2(BASIC_EXAMPLE)?;
 
         assert_eq!(result, 12);
+
+        Ok(())
     }
 
     #[test]
-    fn part2_puzzle_input_should_equal_answer() {
-        let result = calculate_score_part2(INPUT);
+    fn part2_puzzle_input_should_equal_answer() -> ParseResult<()> {
+        let Some(input) = input() else {
+            return Ok(());
+        };
+
+        let result = calculate_score_part2(&input)?;
+
+        assert_answer(2, 2, result);
 
-        assert_eq!(result, 10334);
+        Ok(())
+    }
+
+    #[test]
+    fn unknown_column_value_should_point_at_value() {
+        let err = calculate_score("A Y\nB W").unwrap_err();
+
+        assert_eq!(err.position(), Some((2, 3)));
+        assert_eq!(
+            err.message(),
+            "unknown column value `W`, expected `X`, `Y` or `Z`"
+        );
     }
 }
@@ -1,66 +1,98 @@
 use itertools::Itertools;
+use shared_lib::{
+    diagnostic::{Diagnostic, ParseResult},
+    solution::{Solution, SolutionError, SolutionResult},
+};
 use std::collections::HashSet;
 
 /// Part 1 entrypoint.
-pub fn calculate_items_sum(input: &str) -> u32 {
-    input
-        .lines()
-        .map(|line| line.trim())
-        .map(|line| line.split_at(line.len() / 2))
-        .map(|(a, b)| (a.chars(), b.chars()))
+pub fn calculate_items_sum(input: &str) -> ParseResult<u32> {
+    Ok(parse_input(input)?
+        .iter()
+        .map(|items| items.split_at(items.len() / 2))
         .map(|(a, b)| -> u32 {
-            let a: HashSet<char> = HashSet::from_iter(a);
-            let b: HashSet<char> = HashSet::from_iter(b);
+            let a: HashSet<&u32> = HashSet::from_iter(a);
+            let b: HashSet<&u32> = HashSet::from_iter(b);
 
-            a.iter()
-                .filter(|item| b.contains(item))
-                .map(get_item_value)
-                .sum()
+            a.iter().filter(|item| b.contains(*item)).copied().sum()
         })
-        .sum()
+        .sum())
 }
 
 /// Part 2 entrypoint to calculate common sum of priorities between items shared by the entire group.
 /// Group is delimitated by group_size, but the puzzle sets it at 3.
-pub fn calculate_common_items_sum(input: &str, group_size: usize) -> u32 {
-    input
-        .lines()
+pub fn calculate_common_items_sum(input: &str, group_size: usize) -> ParseResult<u32> {
+    Ok(parse_input(input)?
+        .iter()
         .chunks(group_size)
         .into_iter()
         .map(|group| -> u32 {
             group
-                .map(|line| line.trim())
-                .map(|line| -> HashSet<char> { HashSet::from_iter(line.chars()) })
+                .map(|items| -> HashSet<u32> { items.iter().copied().collect() })
                 .reduce(
-                    |intersection: HashSet<char>, set: HashSet<char>| -> HashSet<char> {
-                        intersection.intersection(&set).cloned().collect()
+                    |intersection: HashSet<u32>, set: HashSet<u32>| -> HashSet<u32> {
+                        intersection.intersection(&set).copied().collect()
                     },
                 )
                 .unwrap_or_default()
                 .iter()
-                .map(get_item_value)
                 .sum()
         })
-        .sum()
+        .sum())
+}
+
+/// Priorities of the items in each rucksack
+fn parse_input(input: &str) -> ParseResult<Vec<Vec<u32>>> {
+    input
+        .lines()
+        .map(|line| parse_rucksack(input, line.trim()))
+        .collect()
+}
+
+/// Priority of every item in `line`, which is a slice of `input`
+fn parse_rucksack(input: &str, line: &str) -> ParseResult<Vec<u32>> {
+    line.char_indices()
+        .map(|(index, item)| get_item_value(input, &line[index..index + item.len_utf8()]))
+        .collect()
 }
 
 const LOWERCASE_COEFFICIENT: u32 = 96;
 const UPPERCASE_COEFFICIENT: u32 = 38;
 
-fn get_item_value(char: &char) -> u32 {
-    let val = *char as u32;
-    let val = match char {
-        'a'..='z' => val - LOWERCASE_COEFFICIENT,
-        'A'..='Z' => val - UPPERCASE_COEFFICIENT,
-        _ => 0,
-    };
+/// Priority of a single `item`, which is a slice of `input`
+fn get_item_value(input: &str, item: &str) -> ParseResult<u32> {
+    match item.chars().next() {
+        Some(char @ 'a'..='z') => Ok(char as u32 - LOWERCASE_COEFFICIENT),
+        Some(char @ 'A'..='Z') => Ok(char as u32 - UPPERCASE_COEFFICIENT),
+        _ => Err(Diagnostic::at(
+            input,
+            item,
+            format!("invalid item `{item}`, expected a letter"),
+        )),
+    }
+}
+
+/// Solution for the Rucksack Reorganization puzzle
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    fn parse(&self, input: &str) -> Option<Result<(), SolutionError>> {
+        Some(parse_input(input).map(drop).map_err(Into::into))
+    }
+
+    fn part1(&self, input: &str) -> SolutionResult {
+        Ok(Box::new(calculate_items_sum(input)?))
+    }
 
-    val
+    fn part2(&self, input: &str) -> SolutionResult {
+        Ok(Box::new(calculate_common_items_sum(input, 3)?))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use shared_lib::answers::assert_answer;
 
     const BASIC_EXAMPLE: &str = "vJrwpWtwJgWrhcsFMMfFFhFp
     jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
@@ -69,33 +101,59 @@ mod tests {
     ttgJtRGJQctTZtZT
     CrZsJsPPZsGzwwsLwLmpwMDw";
 
-    const INPUT: &str = include_str!("../input");
+    fn input() -> Option<String> {
+        shared_lib::input::personal(env!("CARGO_MANIFEST_DIR"))
+    }
 
     #[test]
-    fn basic_example_should_result_in_157() {
-        let result = calculate_items_sum(BASIC_EXAMPLE);
+    fn basic_example_should_result_in_157() -> ParseResult<()> {
+        let result = calculate_items_sum(BASIC_EXAMPLE)?;
+
+        assert_eq!(result, 157);
 
-        assert_eq!(result, 157)
+        Ok(())
     }
 
     #[test]
-    fn part2_basic_example_should_result_in_70() {
-        let result = calculate_common_items_sum(BASIC_EXAMPLE, 3);
+    fn part2_basic_example_should_result_in_70() -> ParseResult<()> {
+        let result = calculate_common_items_sum(BASIC_EXAMPLE, 3)?;
 
-        assert_eq!(result, 70)
+        assert_eq!(result, 70);
+
+        Ok(())
     }
 
     #[test]
-    fn input_should_result_in_value() {
-        let result = calculate_items_sum(INPUT);
+    fn input_should_result_in_value() -> ParseResult<()> {
+        let Some(input) = input() else {
+            return Ok(());
+        };
+
+        let result = calculate_items_sum(&input)?;
+
+        assert_answer(3, 1, result);
+
+        Ok(())
+    }
+
+    #[test]
+    fn part2_input_should_result_in_value() -> ParseResult<()> {
+        let Some(input) = input() else {
+            return Ok(());
+        };
+
+        let result = calculate_common_items_sum(&input, 3)?;
+
+        assert_answer(3, 2, result);
 
-        assert_eq!(result, 8105);
+        Ok(())
     }
 
     #[test]
-    fn part2_input_should_result_in_value() {
-        let result = calculate_common_items_sum(INPUT, 3);
+    fn invalid_item_should_point_at_item() {
+        let err = calculate_items_sum("vJrwpWtwJgWr\nttgJ-RGJ").unwrap_err();
 
-        assert_eq!(result, 2363);
+        assert_eq!(err.position(), Some((2, 5)));
+        assert_eq!(err.message(), "invalid item `-`, expected a letter");
     }
 }
@@ -1,8 +1,8 @@
-use std::{
-    error::Error,
-    fmt::{self, Display, Formatter},
-    ops::RangeInclusive,
-    str::FromStr,
+use std::{ops::RangeInclusive, str::FromStr};
+
+use shared_lib::{
+    diagnostic::{Diagnostic, ParseResult},
+    parse,
 };
 
 pub type Index = u32;
@@ -46,31 +46,10 @@ impl Iterator for IdRange {
     }
 }
 
-#[derive(Debug)]
-pub struct ParseRangeError(String);
-
-impl Display for ParseRangeError {
-    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        write!(
-            f,
-            "ParseRangeError: cannot parse range from string `{}`",
-            self.0
-        )
-    }
-}
-
-impl Error for ParseRangeError {}
-
 impl FromStr for IdRange {
-    type Err = ParseRangeError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if let Some((a, b)) = s.split_once('-') {
-            if let (Ok(min), Ok(max)) = (a.parse(), b.parse()) {
-                return Ok(IdRange::new(min, max));
-            }
-        };
+    type Err = Diagnostic;
 
-        Err(ParseRangeError(s.to_string()))
+    fn from_str(s: &str) -> ParseResult<Self> {
+        Ok(IdRange(parse::range(s, s)?))
     }
 }
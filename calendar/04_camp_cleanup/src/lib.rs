@@ -3,26 +3,28 @@ mod id_range;
 pub use id_range::{IdRange, Index};
 use std::collections::HashSet;
 
+use shared_lib::{
+    diagnostic::ParseResult,
+    parse,
+    solution::{Solution, SolutionError, SolutionResult},
+};
+
 pub type IdSet = HashSet<Index>;
 
 /// Part 1
-pub fn count_ranges(input: &str) -> usize {
-    input
-        .lines()
-        .map(|line| line.trim())
-        .map(parse_id_sets)
-        .filter(subset_or_superset)
-        .count()
+pub fn count_ranges(input: &str) -> ParseResult<usize> {
+    Ok(parse_input(input)?
+        .iter()
+        .filter(|sets| subset_or_superset(sets))
+        .count())
 }
 
 /// Part 2
-pub fn count_ranges_intersect(input: &str) -> usize {
-    input
-        .lines()
-        .map(|line| line.trim())
-        .map(parse_id_sets)
-        .filter(sets_intersect)
-        .count()
+pub fn count_ranges_intersect(input: &str) -> ParseResult<usize> {
+    Ok(parse_input(input)?
+        .iter()
+        .filter(|sets| sets_intersect(sets))
+        .count())
 }
 
 fn sets_intersect((a, b): &(IdSet, IdSet)) -> bool {
@@ -33,21 +35,50 @@ fn subset_or_superset((a, b): &(IdSet, IdSet)) -> bool {
     a.is_subset(b) || a.is_superset(b)
 }
 
-fn parse_id_sets(line: &str) -> (IdSet, IdSet) {
-    let (a, b) = line.split_once(',').unwrap();
-    let range_a: IdRange = a.parse().unwrap();
-    let range_b: IdRange = b.parse().unwrap();
+fn parse_input(input: &str) -> ParseResult<Vec<(IdSet, IdSet)>> {
+    input
+        .lines()
+        .map(|line| line.trim())
+        .map(|line| parse_id_sets(input, line))
+        .collect()
+}
+
+/// Parse a line like `2-4,6-8`, which is a slice of `input`
+fn parse_id_sets(input: &str, line: &str) -> ParseResult<(IdSet, IdSet)> {
+    let (a, b) = parse::pair(input, line, ",")?;
+    let range_a = IdRange::from(parse::range(input, a)?);
+    let range_b = IdRange::from(parse::range(input, b)?);
 
     let set_a: HashSet<Index> = HashSet::from_iter(range_a);
     let set_b: HashSet<Index> = HashSet::from_iter(range_b);
-    (set_a, set_b)
+    Ok((set_a, set_b))
+}
+
+/// Solution for the Camp Cleanup puzzle
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    fn parse(&self, input: &str) -> Option<Result<(), SolutionError>> {
+        Some(parse_input(input).map(drop).map_err(Into::into))
+    }
+
+    fn part1(&self, input: &str) -> SolutionResult {
+        Ok(Box::new(count_ranges(input)?))
+    }
+
+    fn part2(&self, input: &str) -> SolutionResult {
+        Ok(Box::new(count_ranges_intersect(input)?))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use shared_lib::answers::assert_answer;
 
-    const INPUT: &str = include_str!("../input");
+    fn input() -> Option<String> {
+        shared_lib::input::personal(env!("CARGO_MANIFEST_DIR"))
+    }
 
     const BASIC_EXAMPLE: &str = "2-4,6-8
     2-3,4-5
@@ -57,30 +88,53 @@ mod tests {
     2-6,4-8";
 
     #[test]
-    fn basic_example_should_result_in_2() {
-        let result = count_ranges(BASIC_EXAMPLE);
+    fn basic_example_should_result_in_2() -> ParseResult<()> {
+        let result = count_ranges(BASIC_EXAMPLE)?;
+
+        assert_eq!(result, 2);
 
-        assert_eq!(result, 2)
+        Ok(())
     }
 
     #[test]
-    fn part2_basic_example_should_result_in_4() {
-        let result = count_ranges_intersect(BASIC_EXAMPLE);
+    fn part2_basic_example_should_result_in_4() -> ParseResult<()> {
+        let result = count_ranges_intersect(BASIC_EXAMPLE)?;
 
-        assert_eq!(result, 4)
+        assert_eq!(result, 4);
+
+        Ok(())
     }
 
     #[test]
-    fn input_should_result_in_value() {
-        let result = count_ranges(INPUT);
+    fn input_should_result_in_value() -> ParseResult<()> {
+        let Some(input) = input() else {
+            return Ok(());
+        };
+
+        let result = count_ranges(&input)?;
+
+        assert_answer(4, 1, result);
+
+        Ok(())
+    }
+
+    #[test]
+    fn part2_input_should_result_in_value() -> ParseResult<()> {
+        let Some(input) = input() else {
+            return Ok(());
+        };
+
+        let result = count_ranges_intersect(&input)?;
+
+        assert_answer(4, 2, result);
 
-        assert_eq!(result, 573);
+        Ok(())
     }
 
     #[test]
-    fn part2_input_should_result_in_value() {
-        let result = count_ranges_intersect(INPUT);
+    fn invalid_range_should_point_at_section_id() {
+        let err = count_ranges("2-4,6-8\n2-3,4-x").unwrap_err();
 
-        assert_eq!(result, 867);
+        assert_eq!(err.position(), Some((2, 7)));
     }
 }
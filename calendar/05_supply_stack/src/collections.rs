@@ -7,7 +7,9 @@ pub mod cratemap {
         str::FromStr,
     };
 
-    use crate::{error::ParseError, Crate};
+    use shared_lib::diagnostic::{Diagnostic, ParseResult};
+
+    use crate::Crate;
 
     /// Map string keys to crate collections
     #[derive(Debug, PartialEq, Eq, Default)]
@@ -50,13 +52,13 @@ pub mod cratemap {
     }
 
     impl FromStr for CrateMap {
-        type Err = ParseError;
+        type Err = Diagnostic;
 
-        fn from_str(s: &str) -> Result<Self, Self::Err> {
+        fn from_str(s: &str) -> ParseResult<Self> {
             let mut rows = s.lines().rev();
-            let index_row = rows.next().ok_or_else(|| ParseError::ParseStringError {
-                from: s.to_string(),
-            })?;
+            let index_row = rows
+                .next()
+                .ok_or_else(|| Diagnostic::at(s, s, "expected a row of stack numbers"))?;
 
             let mut map = CrateMap::new();
 
@@ -83,12 +85,13 @@ pub mod cratemap {
 
     #[cfg(test)]
     mod tests {
-        use crate::{CrateMap, ParseError};
+        use crate::CrateMap;
+        use shared_lib::diagnostic::Diagnostic;
 
         const BASIC_EXAMPLE: &str = "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 ";
 
         #[test]
-        fn basic_example_should_parse_into_cratemap() -> Result<(), ParseError> {
+        fn basic_example_should_parse_into_cratemap() -> Result<(), Diagnostic> {
             let result: CrateMap = BASIC_EXAMPLE.parse()?;
 
             let expected = {
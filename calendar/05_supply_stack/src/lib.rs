@@ -1,16 +1,19 @@
 mod collections;
-mod error;
 mod procedure;
 
 use std::iter::Extend;
 
+use shared_lib::{
+    diagnostic::Diagnostic,
+    solution::{Solution, SolutionError, SolutionResult},
+};
+
 pub use collections::cratemap::CrateMap;
-use error::{Error, ParseError};
 pub use procedure::{Instruction, Procedure};
 
 pub type Crate = char;
 
-pub type Result<T, E = Error> = std::result::Result<T, E>;
+pub type Result<T, E = Diagnostic> = std::result::Result<T, E>;
 
 pub fn collect_message(map: CrateMap) -> String {
     let mut chars: Vec<(String, char)> = map
@@ -72,19 +75,14 @@ fn move_crates_all(instruction: Instruction, map: &mut CrateMap) {
 
         let at = source.len() - num_crates.min(source.len());
 
-        let values = source.split_off(at);
-
-        values
+        source.split_off(at)
     };
 
     map.entry(target.clone()).or_default().extend(source_values);
 }
 
 pub fn rearrange_crates(input: &str) -> Result<CrateMap> {
-    let (procedure, mut map) = match parse_input(input) {
-        Ok(val) => val,
-        Err(e) => return Err(Error::from(e)),
-    };
+    let (procedure, mut map) = parse_input(input)?;
 
     for instruction in procedure {
         move_crates_one_by_one(instruction, &mut map);
@@ -95,10 +93,7 @@ pub fn rearrange_crates(input: &str) -> Result<CrateMap> {
 
 /// Part 2 - using a queue data structure to retain order
 pub fn rearrange_crates_part2(input: &str) -> Result<CrateMap> {
-    let (procedure, mut map) = match parse_input(input) {
-        Ok(val) => val,
-        Err(e) => return Err(Error::from(e)),
-    };
+    let (procedure, mut map) = parse_input(input)?;
 
     for instruction in procedure {
         move_crates_all(instruction, &mut map)
@@ -112,21 +107,50 @@ pub fn rearrange_crates_part2(input: &str) -> Result<CrateMap> {
 /// * Top half is a map of the crate stacks - [`CrateMap`]
 /// * Bottom half is instructions to rearrange the stacks - [`Procedure`]
 ///
-pub fn parse_input(input: &str) -> Result<(Procedure, CrateMap), ParseError> {
-    let input_parts = input
-        .split_once("\n\n")
-        .expect(r"input should be separated by 2 newlines `\n\n`");
-
-    let map: CrateMap = input_parts.0.parse()?;
-    let procedure: Procedure = input_parts.1.parse()?;
+pub fn parse_input(input: &str) -> Result<(Procedure, CrateMap)> {
+    let (crates, instructions) = input.split_once("\n\n").ok_or_else(|| {
+        Diagnostic::at(
+            input,
+            &input[input.len()..],
+            "expected a blank line between the crates and the procedure",
+        )
+    })?;
+
+    let map: CrateMap = crates
+        .parse()
+        .map_err(|err: Diagnostic| err.within(input, crates))?;
+    let procedure: Procedure = instructions
+        .parse()
+        .map_err(|err: Diagnostic| err.within(input, instructions))?;
 
     Ok((procedure, map))
 }
 
+/// Solution for the Supply Stacks puzzle
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    fn parse(&self, input: &str) -> Option<Result<(), SolutionError>> {
+        Some(parse_input(input).map(drop).map_err(Into::into))
+    }
+
+    fn part1(&self, input: &str) -> SolutionResult {
+        Ok(Box::new(collect_message(rearrange_crates(input)?)))
+    }
+
+    fn part2(&self, input: &str) -> SolutionResult {
+        Ok(Box::new(collect_message(rearrange_crates_part2(input)?)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::{collect_message, rearrange_crates, rearrange_crates_part2, Result};
-    const INPUT: &str = include_str!("../input");
+    use shared_lib::answers::assert_answer;
+
+    fn input() -> Option<String> {
+        shared_lib::input::personal(env!("CARGO_MANIFEST_DIR"))
+    }
 
     const BASIC_EXAMPLE: &str = "    [D]    
 [N] [C]    
@@ -150,10 +174,14 @@ move 1 from 1 to 2";
 
     #[test]
     fn input_should_result_in_value() -> Result<()> {
-        let map = rearrange_crates(INPUT)?;
+        let Some(input) = input() else {
+            return Ok(());
+        };
+
+        let map = rearrange_crates(&input)?;
         let message = collect_message(map);
 
-        assert_eq!(message, "ZBDRNPMVH");
+        assert_answer(5, 1, message);
 
         Ok(())
     }
@@ -170,11 +198,25 @@ move 1 from 1 to 2";
 
     #[test]
     fn part2_input_should_result_in_value() -> Result<()> {
-        let map = rearrange_crates_part2(INPUT)?;
+        let Some(input) = input() else {
+            return Ok(());
+        };
+
+        let map = rearrange_crates_part2(&input)?;
         let message = collect_message(map);
 
-        assert_eq!(message, "WDLPFNNNB");
+        assert_answer(5, 2, message);
 
         Ok(())
     }
+
+    #[test]
+    fn invalid_instruction_should_point_at_word() {
+        let input = BASIC_EXAMPLE.replace("move 2 from", "move 2 form");
+
+        let err = rearrange_crates(&input).unwrap_err();
+
+        assert_eq!(err.position(), Some((8, 8)));
+        assert_eq!(err.message(), "expected `from`, found `form`");
+    }
 }
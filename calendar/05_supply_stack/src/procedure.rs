@@ -1,6 +1,6 @@
 use std::{str::FromStr, vec::IntoIter};
 
-use crate::error::ParseError;
+use shared_lib::diagnostic::{Diagnostic, ParseResult};
 
 /// String-parsable struct representing a single instruction
 ///
@@ -36,25 +36,46 @@ impl Instruction {
 }
 
 impl FromStr for Instruction {
-    type Err = ParseError;
+    type Err = Diagnostic;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut parts = s.split(' ');
+    fn from_str(s: &str) -> ParseResult<Self> {
+        let mut words = s.split(' ');
 
-        let mut next = || -> Result<&str, Self::Err> {
-            parts.nth(1).ok_or_else(|| ParseError::ParseStringError {
-                from: s.to_string(),
-            })
-        };
+        let num_crates = field(s, &mut words, "move")?;
+        let source = field(s, &mut words, "from")?;
+        let target = field(s, &mut words, "to")?;
 
         Ok(Instruction {
-            num_crates: next()?.parse().unwrap_or_default(),
-            source: next()?.to_string(),
-            target: next()?.to_string(),
+            num_crates: num_crates.parse().map_err(|err| {
+                Diagnostic::at(s, num_crates, format!("invalid number of crates: {err}"))
+            })?,
+            source: source.to_string(),
+            target: target.to_string(),
         })
     }
 }
 
+/// Value following `keyword` in the words of an instruction
+fn field<'a>(
+    s: &'a str,
+    words: &mut impl Iterator<Item = &'a str>,
+    keyword: &str,
+) -> ParseResult<&'a str> {
+    let end = &s[s.len()..];
+
+    match words.next() {
+        Some(word) if word == keyword => words
+            .next()
+            .ok_or_else(|| Diagnostic::at(s, end, format!("expected a value after `{keyword}`"))),
+        Some(word) => Err(Diagnostic::at(
+            s,
+            word,
+            format!("expected `{keyword}`, found `{word}`"),
+        )),
+        None => Err(Diagnostic::at(s, end, format!("expected `{keyword}`"))),
+    }
+}
+
 /// String-parsable [`Vec<Instruction>`] wrapper
 ///
 /// # Example
@@ -104,16 +125,14 @@ impl IntoIterator for Procedure {
 }
 
 impl FromStr for Procedure {
-    type Err = ParseError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let instruction_results: Result<Vec<Instruction>, ParseError> =
-            s.lines().map(|line| line.parse::<Instruction>()).collect();
+    type Err = Diagnostic;
 
-        let instructions = instruction_results.map_err(|_| ParseError::ParseStringError {
-            from: s.to_string(),
-        })?;
-
-        Ok(Procedure::from_iter(instructions))
+    fn from_str(s: &str) -> ParseResult<Self> {
+        s.lines()
+            .map(|line| {
+                line.parse::<Instruction>()
+                    .map_err(|err| err.within(s, line))
+            })
+            .collect()
     }
 }
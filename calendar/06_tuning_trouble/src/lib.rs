@@ -1,5 +1,7 @@
 use std::{collections::HashSet, ops::Range};
 
+use shared_lib::solution::{Solution, SolutionResult};
+
 /// Dynamic sliding window
 pub struct Window {
     /// index of the first char in the window
@@ -60,25 +62,42 @@ pub fn find_sop_marker(input: &str, window_width: u8) -> usize {
     input.len()
 }
 
+/// Solution for the Tuning Trouble puzzle
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    fn part1(&self, input: &str) -> SolutionResult {
+        Ok(Box::new(find_sop_marker(input, 4)))
+    }
+
+    fn part2(&self, input: &str) -> SolutionResult {
+        Ok(Box::new(find_sop_marker(input, 14)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::find_sop_marker;
+    use shared_lib::answers::assert_answer;
 
     const WINDOW_WIDTH: u8 = 4;
     const WINDOW_WIDTH_PART2: u8 = 14;
 
-    const INPUTS: [&str; 6] = [
+    const INPUTS: [&str; 5] = [
         "mjqjpqmgbljsphdztnvjfqwrcgsmlb",
         "bvwbjplbgvbhsrlpgdmjqwftvncz",
         "nppdvjthqldpwncqszvftbrmjlhg",
         "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg",
         "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw",
-        include_str!("../input"),
     ];
 
-    const RESULTS: [usize; 6] = [7, 5, 6, 10, 11, 1804];
+    fn input() -> Option<String> {
+        shared_lib::input::personal(env!("CARGO_MANIFEST_DIR"))
+    }
+
+    const RESULTS: [usize; 5] = [7, 5, 6, 10, 11];
 
-    const RESULTS_PART2: [usize; 6] = [19, 23, 23, 29, 26, 2508];
+    const RESULTS_PART2: [usize; 5] = [19, 23, 23, 29, 26];
 
     #[test]
     fn find_sop_marker_should_take_input_and_find_result() {
@@ -86,6 +105,10 @@ mod tests {
             let result = find_sop_marker(input, WINDOW_WIDTH);
             assert_eq!(result, *expected_result)
         }
+
+        if let Some(input) = input() {
+            assert_answer(6, 1, find_sop_marker(&input, WINDOW_WIDTH));
+        }
     }
 
     #[test]
@@ -94,5 +117,9 @@ mod tests {
             let result = find_sop_marker(input, WINDOW_WIDTH_PART2);
             assert_eq!(result, *expected_result)
         }
+
+        if let Some(input) = input() {
+            assert_answer(6, 2, find_sop_marker(&input, WINDOW_WIDTH_PART2));
+        }
     }
 }
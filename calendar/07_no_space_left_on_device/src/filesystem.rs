@@ -41,7 +41,7 @@ impl NodeTable {
         self.add(node)
     }
 
-    pub fn iter(&self) -> Iter<Node> {
+    pub fn iter(&self) -> Iter<'_, Node> {
         self.nodes.iter()
     }
 }
@@ -1,8 +1,26 @@
 mod filesystem;
 
+use shared_lib::{
+    diagnostic::{Diagnostic, ParseResult},
+    solution::{Solution, SolutionError, SolutionResult},
+};
+
 use filesystem::{Node, NodeTable};
 
-fn parse_input(input: &str) -> anyhow::Result<NodeTable> {
+/// Next word of `line`, or a diagnostic pointing at its end naming what was
+/// `expected`
+fn next_word<'a>(
+    input: &str,
+    line: &'a str,
+    parts: &mut impl Iterator<Item = &'a str>,
+    expected: &str,
+) -> ParseResult<&'a str> {
+    parts
+        .next()
+        .ok_or_else(|| Diagnostic::at(input, &line[line.len()..], format!("expected {expected}")))
+}
+
+fn parse_input(input: &str) -> ParseResult<NodeTable> {
     let mut nodes = NodeTable::new();
 
     let mut current_dir_index = nodes.add_dir("/");
@@ -10,12 +28,12 @@ fn parse_input(input: &str) -> anyhow::Result<NodeTable> {
     for line in input.trim().lines() {
         let mut parts = line.split(' ');
 
-        match parts.nth(0).unwrap() {
-            "$" => match parts.nth(0).unwrap() {
+        match next_word(input, line, &mut parts, "a command or a listing")? {
+            "$" => match next_word(input, line, &mut parts, "a command")? {
                 "cd" => {
                     let node = &nodes[current_dir_index];
 
-                    let target_dir = parts.nth(0).unwrap();
+                    let target_dir = next_word(input, line, &mut parts, "a directory")?;
 
                     match target_dir {
                         ".." => {
@@ -29,11 +47,13 @@ fn parse_input(input: &str) -> anyhow::Result<NodeTable> {
                                 let child =
                                     cwd.iter().find(|&child| nodes[*child].name() == target_dir);
 
-                                if let Some(target) = child {
-                                    current_dir_index = *target
-                                } else {
-                                    eprintln!("directory {target_dir} was not found.")
-                                }
+                                current_dir_index = *child.ok_or_else(|| {
+                                    Diagnostic::at(
+                                        input,
+                                        target_dir,
+                                        format!("directory `{target_dir}` was not found"),
+                                    )
+                                })?;
                             }
                         }
                     }
@@ -43,7 +63,7 @@ fn parse_input(input: &str) -> anyhow::Result<NodeTable> {
                 _ => continue,
             },
             "dir" => {
-                let target_dir = parts.nth(0).unwrap();
+                let target_dir = next_word(input, line, &mut parts, "a directory name")?;
 
                 let index = nodes.add_dir(target_dir);
 
@@ -54,9 +74,11 @@ fn parse_input(input: &str) -> anyhow::Result<NodeTable> {
                 }
             }
             value if value.chars().all(char::is_numeric) => {
-                let size: u64 = value.parse()?;
+                let size: u64 = value.parse().map_err(|err| {
+                    Diagnostic::at(input, value, format!("invalid file size `{value}`: {err}"))
+                })?;
 
-                let name = parts.nth(0).unwrap();
+                let name = next_word(input, line, &mut parts, "a file name")?;
 
                 let index = nodes.add_file(name, size);
 
@@ -75,7 +97,7 @@ fn parse_input(input: &str) -> anyhow::Result<NodeTable> {
 
 const SIZE_LIMIT_PART1: u64 = 100_100;
 
-pub fn calculate_dir_sums_under_limit(input: &str) -> anyhow::Result<u64> {
+pub fn calculate_dir_sums_under_limit(input: &str) -> ParseResult<u64> {
     let node_table = parse_input(input)?;
 
     let total: u64 = node_table
@@ -94,7 +116,7 @@ const MAXIMUM_SPACE_FOR_USE: u64 = TOTAL_AVAILABLE_SPACE - MINIMUM_SPACE_NEEDED;
 
 /// **Part 2** - Find a directory to delete; One that lets us reach our
 /// goal with the minimal possible
-pub fn find_smallest_viable_dir_size(input: &str) -> anyhow::Result<u64> {
+pub fn find_smallest_viable_dir_size(input: &str) -> ParseResult<u64> {
     let node_table = parse_input(input)?;
 
     let total_space_used = node_table[0.into()].size(&node_table);
@@ -119,9 +141,27 @@ pub fn find_smallest_viable_dir_size(input: &str) -> anyhow::Result<u64> {
     Ok(dir_size)
 }
 
+/// Solution for the No Space Left On Device puzzle
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    fn parse(&self, input: &str) -> Option<Result<(), SolutionError>> {
+        Some(parse_input(input).map(drop).map_err(Into::into))
+    }
+
+    fn part1(&self, input: &str) -> SolutionResult {
+        Ok(Box::new(calculate_dir_sums_under_limit(input)?))
+    }
+
+    fn part2(&self, input: &str) -> SolutionResult {
+        Ok(Box::new(find_smallest_viable_dir_size(input)?))
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{calculate_dir_sums_under_limit, find_smallest_viable_dir_size};
+    use crate::{calculate_dir_sums_under_limit, find_smallest_viable_dir_size, ParseResult};
+    use shared_lib::answers::assert_answer;
 
     const BASIC_INPUT: &str = "$ cd /
 $ ls
@@ -147,37 +187,44 @@ $ ls
 5626152 d.ext
 7214296 k";
 
-    const INPUTS: [&str; 2] = [BASIC_INPUT, include_str!("../input")];
-
-    const RESULTS: [u64; 2] = [95437, 1642503];
+    fn input() -> Option<String> {
+        shared_lib::input::personal(env!("CARGO_MANIFEST_DIR"))
+    }
 
     #[test]
-    fn should_take_input_and_find_result() -> anyhow::Result<()> {
-        let mut i = 0;
-        for input in INPUTS {
-            let total = calculate_dir_sums_under_limit(input)?;
-
-            assert_eq!(total, RESULTS[i]);
+    fn should_take_input_and_find_result() -> ParseResult<()> {
+        assert_eq!(calculate_dir_sums_under_limit(BASIC_INPUT)?, 95437);
 
-            i += 1;
+        if let Some(input) = input() {
+            assert_answer(7, 1, calculate_dir_sums_under_limit(&input)?);
         }
 
         Ok(())
     }
 
-    const RESULTS_PART2: [u64; 2] = [24933642, 6999588];
-
     #[test]
-    fn part2_should_take_input_and_find_result() -> anyhow::Result<()> {
-        let mut i = 0;
-        for input in INPUTS {
-            let size = find_smallest_viable_dir_size(input)?;
+    fn part2_should_take_input_and_find_result() -> ParseResult<()> {
+        assert_eq!(find_smallest_viable_dir_size(BASIC_INPUT)?, 24933642);
 
-            assert_eq!(size, RESULTS_PART2[i]);
-
-            i += 1;
+        if let Some(input) = input() {
+            assert_answer(7, 2, find_smallest_viable_dir_size(&input)?);
         }
 
         Ok(())
     }
+
+    #[test]
+    fn missing_file_name_should_point_at_end_of_line() {
+        let err = calculate_dir_sums_under_limit("$ cd /\n$ ls\n584").unwrap_err();
+
+        assert_eq!(err.position(), Some((3, 4)));
+        assert_eq!(err.message(), "expected a file name");
+    }
+
+    #[test]
+    fn unknown_directory_should_point_at_name() {
+        let err = calculate_dir_sums_under_limit("$ cd /\n$ cd nope").unwrap_err();
+
+        assert_eq!(err.position(), Some((2, 6)));
+    }
 }
@@ -1,13 +1,10 @@
-use std::{
-    error::Error,
-    fmt::{self, Display, Formatter},
-    num::ParseIntError,
-    str::FromStr,
-};
+use std::{num::ParseIntError, str::FromStr};
 
 use shared_lib::{
-    grid::{Grid, GridLike, ParseGridError},
+    diagnostic::Diagnostic,
+    grid::{Direction, Grid, GridLike},
     point::Point,
+    solution::{Solution, SolutionError, SolutionResult},
 };
 
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
@@ -15,25 +12,8 @@ pub struct Tree {
     height: u8,
 }
 
-#[derive(Debug)]
-pub struct ParseTreeError(ParseIntError);
-
-impl From<ParseIntError> for ParseTreeError {
-    fn from(err: ParseIntError) -> Self {
-        ParseTreeError(err)
-    }
-}
-
-impl Display for ParseTreeError {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        write!(f, "ParseTreeError: failed to parse `Tree`\n\n{}", self.0)
-    }
-}
-
-impl Error for ParseTreeError {}
-
 impl FromStr for Tree {
-    type Err = ParseTreeError;
+    type Err = ParseIntError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let height: u8 = s.parse()?;
@@ -42,10 +22,21 @@ impl FromStr for Tree {
     }
 }
 
-fn is_edge(grid: &Grid<Tree>, point: Point) -> bool {
-    let (width, height) = (grid.width(), grid.height());
+/// Number of trees seen from `point` looking in `direction`, stopping at the
+/// edge or at the first tree that is the same height or taller
+fn viewing_distance(grid: &Grid<Tree>, point: Point, direction: Direction) -> usize {
+    let target_tree = &grid[point];
+    let mut distance = 0;
+
+    for tree in grid.ray(point, direction) {
+        distance += 1;
+
+        if tree >= target_tree {
+            break;
+        }
+    }
 
-    point.x == 0 || point.y == 0 || point.x == width - 1 || point.y == height - 1
+    distance
 }
 
 /// Calculate the scenic score for a tree at `point`
@@ -57,51 +48,10 @@ fn is_edge(grid: &Grid<Tree>, point: Point) -> bool {
 /// from that tree; stop if you reach an edge or at the first tree that is the same
 /// height or taller than the tree under consideration.
 fn calculate_scenic_score(grid: &Grid<Tree>, point: Point) -> usize {
-    let (width, height) = (grid.width(), grid.height());
-
-    if is_edge(grid, point) {
-        return 0;
-    }
-
-    let target_tree = grid.get(point);
-
-    let mut left = 0usize;
-    for x in (0..point.x).rev() {
-        left += 1;
-        let tree = grid.get((x, point.y).into());
-        if tree >= target_tree {
-            break;
-        }
-    }
-
-    let mut right = 0usize;
-    for x in point.x + 1..width {
-        right += 1;
-        let tree = grid.get((x, point.y).into());
-        if tree >= target_tree {
-            break;
-        }
-    }
-
-    let mut up = 0usize;
-    for y in (0..point.y).rev() {
-        up += 1;
-        let tree = grid.get((point.x, y).into());
-        if tree >= target_tree {
-            break;
-        }
-    }
-
-    let mut down = 0usize;
-    for y in point.y + 1..height {
-        down += 1;
-        let tree = grid.get((point.x, y).into());
-        if tree >= target_tree {
-            break;
-        }
-    }
-
-    left * right * down * up
+    Direction::ALL
+        .into_iter()
+        .map(|direction| viewing_distance(grid, point, direction))
+        .product()
 }
 
 /// Check if the tree at `point` is visible
@@ -109,28 +59,11 @@ fn calculate_scenic_score(grid: &Grid<Tree>, point: Point) -> usize {
 /// A tree is **visible** if all of the other trees between it
 /// and an edge of the grid are **shorter** than it.
 pub fn check_tree_visibility(grid: &Grid<Tree>, point: Point) -> bool {
-    let (width, height) = (grid.width(), grid.height());
-
-    if is_edge(grid, point) {
-        return true;
-    }
-
-    let target_tree = grid.get(point);
-
-    let check_x = |x| {
-        let tree = grid.get((x, point.y).into());
-        tree < target_tree
-    };
-
-    let check_y = |y| {
-        let tree = grid.get((point.x, y).into());
-        tree < target_tree
-    };
+    let target_tree = &grid[point];
 
-    (0..point.x).rev().all(check_x)
-        || (point.x + 1..width).all(check_x)
-        || (0..point.y).rev().all(check_y)
-        || (point.y + 1..height).all(check_y)
+    Direction::ALL
+        .into_iter()
+        .any(|direction| grid.ray(point, direction).all(|tree| tree < target_tree))
 }
 
 impl Tree {
@@ -139,51 +72,60 @@ impl Tree {
     }
 }
 
-pub fn count_visible_trees(input: &str) -> Result<u64, ParseGridError> {
+pub fn count_visible_trees(input: &str) -> Result<u64, Diagnostic> {
     let grid = input.parse::<Grid<Tree>>()?;
 
-    let mut count = 0;
-
-    for x in 0..grid.width() {
-        for y in 0..grid.height() {
-            let tree_is_visible = check_tree_visibility(&grid, (x, y).into());
-
-            if tree_is_visible {
-                count += 1;
-            }
-        }
-    }
+    let count = grid
+        .points()
+        .filter(|&point| check_tree_visibility(&grid, point))
+        .count();
 
-    Ok(count)
+    Ok(count as u64)
 }
 
-pub fn find_max_scenic_score(input: &str) -> Result<usize, ParseGridError> {
+pub fn find_max_scenic_score(input: &str) -> Result<usize, Diagnostic> {
     let grid = input.parse::<Grid<Tree>>()?;
 
-    let mut max_scenic_score = 0;
+    let max_scenic_score = grid
+        .points()
+        .map(|point| calculate_scenic_score(&grid, point))
+        .max()
+        .unwrap_or_default();
 
-    for x in 0..grid.width() {
-        for y in 0..grid.height() {
-            let scenic_score = calculate_scenic_score(&grid, (x, y).into());
+    Ok(max_scenic_score)
+}
 
-            max_scenic_score = scenic_score.max(max_scenic_score)
-        }
+/// Solution for the Treetop Tree House puzzle
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    fn parse(&self, input: &str) -> Option<Result<(), SolutionError>> {
+        Some(input.parse::<Grid<Tree>>().map(drop).map_err(Into::into))
     }
 
-    Ok(max_scenic_score)
+    fn part1(&self, input: &str) -> SolutionResult {
+        Ok(Box::new(count_visible_trees(input)?))
+    }
+
+    fn part2(&self, input: &str) -> SolutionResult {
+        Ok(Box::new(find_max_scenic_score(input)?))
+    }
 }
 
 #[cfg(test)]
 mod tests {
 
-    use super::{count_visible_trees, find_max_scenic_score, ParseGridError};
+    use super::{count_visible_trees, find_max_scenic_score, Diagnostic};
+    use shared_lib::answers::assert_answer;
 
     const BASIC_INPUT: &str = "30373\n25512\n65332\n33549\n35390";
 
-    const INPUT: &str = include_str!("../input");
+    fn input() -> Option<String> {
+        shared_lib::input::personal(env!("CARGO_MANIFEST_DIR"))
+    }
 
     #[test]
-    fn basic_input_should_result_in_21() -> Result<(), ParseGridError> {
+    fn basic_input_should_result_in_21() -> Result<(), Diagnostic> {
         let result = count_visible_trees(BASIC_INPUT)?;
 
         assert_eq!(result, 21);
@@ -192,16 +134,20 @@ mod tests {
     }
 
     #[test]
-    fn input_should_result_in_value() -> Result<(), ParseGridError> {
-        let result = count_visible_trees(INPUT)?;
+    fn input_should_result_in_value() -> Result<(), Diagnostic> {
+        let Some(input) = input() else {
+            return Ok(());
+        };
+
+        let result = count_visible_trees(&input)?;
 
-        assert_eq!(result, 1560);
+        assert_answer(8, 1, result);
 
         Ok(())
     }
 
     #[test]
-    fn part2_basic_input_should_result_in_8() -> Result<(), ParseGridError> {
+    fn part2_basic_input_should_result_in_8() -> Result<(), Diagnostic> {
         let result = find_max_scenic_score(BASIC_INPUT)?;
 
         assert_eq!(result, 8);
@@ -210,11 +156,22 @@ mod tests {
     }
 
     #[test]
-    fn part2_input_should_result_in_value() -> Result<(), ParseGridError> {
-        let result = find_max_scenic_score(INPUT)?;
+    fn part2_input_should_result_in_value() -> Result<(), Diagnostic> {
+        let Some(input) = input() else {
+            return Ok(());
+        };
 
-        assert_eq!(result, 252000);
+        let result = find_max_scenic_score(&input)?;
+
+        assert_answer(8, 2, result);
 
         Ok(())
     }
+
+    #[test]
+    fn invalid_tree_should_point_at_cell() {
+        let err = count_visible_trees("303\n2x5").unwrap_err();
+
+        assert_eq!(err.position(), Some((2, 2)));
+    }
 }
@@ -1,12 +1,8 @@
-use std::{
-    collections::HashSet,
-    error::Error,
-    fmt::{self, Display, Formatter},
-    num::ParseIntError,
-    str::FromStr,
-};
+use std::{collections::HashSet, str::FromStr};
 
+use shared_lib::diagnostic::{Diagnostic, ParseResult};
 use shared_lib::point::{self, Relative};
+use shared_lib::solution::{Solution, SolutionError, SolutionResult};
 
 type Point = point::Point<isize>;
 
@@ -21,15 +17,15 @@ pub enum Direction {
 pub type Motion = (Direction, u8);
 
 impl FromStr for Direction {
-    type Err = ParseError;
+    type Err = Diagnostic;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+    fn from_str(s: &str) -> ParseResult<Self> {
         let direction = match s {
             "R" => Direction::Right,
             "L" => Direction::Left,
             "U" => Direction::Up,
             "D" => Direction::Down,
-            _ => return Err(ParseError::Direction(s.to_string())),
+            _ => return Err(Diagnostic::at(s, s, format!("unknown direction `{s}`"))),
         };
 
         Ok(direction)
@@ -117,63 +113,33 @@ impl Rope {
 /// # Tail Movement Rules
 ///
 /// * If `head` is ever two steps directly up, down, left, or right
-/// from the `tail`, the `tail` **must** also move one step in that direction
+///   from the `tail`, the `tail` **must** also move one step in that direction
 ///
 /// * If `head` and `tail` aren't touching, and aren't in the same row
-/// and column, the `tail` **always** moves one step diagonally to keep up
+///   and column, the `tail` **always** moves one step diagonally to keep up
 fn move_rope(rope: &mut Rope, direction: &Direction) {
     rope.move_head(direction);
     rope.move_tail();
 }
 
-#[derive(Debug)]
-pub enum ParseError {
-    Direction(String),
-    Steps(ParseIntError),
-    Input(String),
-}
-
-impl Error for ParseError {}
-
-impl Display for ParseError {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        match self {
-            ParseError::Direction(value) => {
-                write!(f, "ParseError: failed to parse direction from\n\n`{value}`")
-            }
-            ParseError::Input(value) => {
-                writeln!(f, "ParseError: failed to parse input from\n\n`{value}`")
-            }
-            ParseError::Steps(value) => {
-                writeln!(
-                    f,
-                    "ParseError: failed to parse steps\n\nCaused by: `{value}`"
-                )
-            }
-        }
-    }
-}
-
-impl From<ParseIntError> for ParseError {
-    fn from(err: ParseIntError) -> Self {
-        ParseError::Steps(err)
-    }
-}
-
-pub fn parse_input(input: &str) -> Result<Vec<Motion>, ParseError> {
+pub fn parse_input(input: &str) -> ParseResult<Vec<Motion>> {
     input
         .trim()
         .lines()
         .map(|line| {
-            line.trim()
-                .split_once(' ')
-                .ok_or_else(|| ParseError::Input(input.to_string()))
-                .and_then(|(raw_direction, raw_steps)| {
-                    Ok((
-                        raw_direction.parse::<Direction>()?,
-                        raw_steps.parse::<u8>()?,
-                    ))
-                })
+            let line = line.trim();
+            let (raw_direction, raw_steps) = line.split_once(' ').ok_or_else(|| {
+                Diagnostic::at(input, line, "expected a direction and a number of steps")
+            })?;
+
+            let direction = raw_direction
+                .parse::<Direction>()
+                .map_err(|err| err.within(input, raw_direction))?;
+            let steps = raw_steps.parse::<u8>().map_err(|err| {
+                Diagnostic::at(input, raw_steps, format!("invalid number of steps: {err}"))
+            })?;
+
+            Ok((direction, steps))
         })
         .collect()
 }
@@ -193,7 +159,7 @@ fn count_points_tails_visited(moves: Vec<Motion>, rope: &mut Rope) -> usize {
     set.len()
 }
 
-pub fn part1_count_points_tail_visited(input: &str) -> Result<usize, ParseError> {
+pub fn part1_count_points_tail_visited(input: &str) -> ParseResult<usize> {
     let moves = parse_input(input)?;
 
     let mut rope = Rope::new(2);
@@ -203,7 +169,7 @@ pub fn part1_count_points_tail_visited(input: &str) -> Result<usize, ParseError>
     Ok(result)
 }
 
-pub fn part2_count_points_tails_visited(input: &str) -> Result<usize, ParseError> {
+pub fn part2_count_points_tails_visited(input: &str) -> ParseResult<usize> {
     let moves = parse_input(input)?;
 
     let mut rope = Rope::new(10);
@@ -213,18 +179,38 @@ pub fn part2_count_points_tails_visited(input: &str) -> Result<usize, ParseError
     Ok(result)
 }
 
+/// Solution for the Rope Bridge puzzle
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    fn parse(&self, input: &str) -> Option<Result<(), SolutionError>> {
+        Some(parse_input(input).map(drop).map_err(Into::into))
+    }
+
+    fn part1(&self, input: &str) -> SolutionResult {
+        Ok(Box::new(part1_count_points_tail_visited(input)?))
+    }
+
+    fn part2(&self, input: &str) -> SolutionResult {
+        Ok(Box::new(part2_count_points_tails_visited(input)?))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use shared_lib::answers::assert_answer;
 
     const BASIC_INPUT: &str = "R 4\nU 4\nL 3\nD 1\nR 4\nD 1\nL 5\nR 2";
 
     const LARGE_INPUT: &str = "R 5\nU 8\nL 8\nD 3\nR 17\nD 10\nL 25\nU 20";
 
-    const INPUT: &str = include_str!("../input");
+    fn input() -> Option<String> {
+        shared_lib::input::personal(env!("CARGO_MANIFEST_DIR"))
+    }
 
     #[test]
-    fn basic_input_should_eq_13() -> Result<(), ParseError> {
+    fn basic_input_should_eq_13() -> ParseResult<()> {
         let result = part1_count_points_tail_visited(BASIC_INPUT)?;
 
         assert_eq!(result, 13);
@@ -233,16 +219,20 @@ mod tests {
     }
 
     #[test]
-    fn input_should_eq_value() -> Result<(), ParseError> {
-        let result = part1_count_points_tail_visited(INPUT)?;
+    fn input_should_eq_value() -> ParseResult<()> {
+        let Some(input) = input() else {
+            return Ok(());
+        };
+
+        let result = part1_count_points_tail_visited(&input)?;
 
-        assert_eq!(result, 6212);
+        assert_answer(9, 1, result);
 
         Ok(())
     }
 
     #[test]
-    fn part_2_basic_input_should_eq_1() -> Result<(), ParseError> {
+    fn part_2_basic_input_should_eq_1() -> ParseResult<()> {
         let result = part2_count_points_tails_visited(BASIC_INPUT)?;
 
         assert_eq!(result, 1);
@@ -251,7 +241,7 @@ mod tests {
     }
 
     #[test]
-    fn part_2_large_input_should_eq_36() -> Result<(), ParseError> {
+    fn part_2_large_input_should_eq_36() -> ParseResult<()> {
         let result = part2_count_points_tails_visited(LARGE_INPUT)?;
 
         assert_eq!(result, 36);
@@ -260,11 +250,25 @@ mod tests {
     }
 
     #[test]
-    fn part_2_input_should_eq_value() -> Result<(), ParseError> {
-        let result = part2_count_points_tails_visited(INPUT)?;
+    fn part_2_input_should_eq_value() -> ParseResult<()> {
+        let Some(input) = input() else {
+            return Ok(());
+        };
+
+        let result = part2_count_points_tails_visited(&input)?;
 
-        assert_eq!(result, 2522);
+        assert_answer(9, 2, result);
 
         Ok(())
     }
+
+    #[test]
+    fn invalid_direction_should_point_at_line() {
+        let err = parse_input("R 4\nU 4\nX 3").unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "error: unknown direction `X`\n --> 3:1\n  |\n3 | X 3\n  | ^"
+        );
+    }
 }
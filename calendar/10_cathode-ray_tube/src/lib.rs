@@ -1,16 +1,23 @@
-use std::{
-    error,
-    fmt::{self, Display, Formatter},
-    num::ParseIntError,
+use shared_lib::{
+    diagnostic::{Diagnostic, ParseResult},
+    grid::{Grid, GridLikeMut},
+    point::Point,
+    solution::{Solution, SolutionError, SolutionResult},
 };
 
+/// Width of the CRT screen in pixels
+const SCREEN_WIDTH: usize = 40;
+
+/// Height of the CRT screen in pixels
+const SCREEN_HEIGHT: usize = 6;
+
 #[derive(Debug)]
-struct CPU {
+struct Cpu {
     x: i32,
     cycle: u16,
 }
 
-impl CPU {
+impl Cpu {
     fn new() -> Self {
         Self { x: 1, cycle: 0 }
     }
@@ -46,7 +53,7 @@ impl CPU {
     }
 
     fn draw(&mut self, program: &[Instruction]) -> String {
-        let mut screen = String::new();
+        let mut screen = Grid::new(SCREEN_WIDTH, SCREEN_HEIGHT);
 
         for instruction in program {
             match instruction {
@@ -65,20 +72,19 @@ impl CPU {
             }
         }
 
-        screen.trim().to_string()
+        screen.render_with(|&lit| if lit { '#' } else { '.' })
     }
 
-    fn draw_pixel(&self, display: &mut String) {
-        let line_index = ((self.cycle - 1) % 40) as i32;
+    /// Light the pixel drawn during the current cycle if the sprite covers it
+    fn draw_pixel(&self, screen: &mut Grid<bool>) {
+        let index = (self.cycle - 1) as usize;
+        let point = Point {
+            x: index % SCREEN_WIDTH,
+            y: index / SCREEN_WIDTH,
+        };
 
-        if (self.x - 1..=self.x + 1).contains(&line_index) {
-            display.push('#');
-        } else {
-            display.push('.');
-        }
-
-        if self.cycle % 40 == 0 {
-            display.push('\n');
+        if let Some(pixel) = screen.get_mut(point) {
+            *pixel = (self.x - 1..=self.x + 1).contains(&(point.x as i32));
         }
     }
 
@@ -93,80 +99,77 @@ pub enum Instruction {
     Addx(i32),
 }
 
-#[derive(Debug)]
-pub enum Error {
-    ParseInstructionError(String),
-}
-
-impl Display for Error {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        match self {
-            Self::ParseInstructionError(err) => {
-                write!(
-                    f,
-                    "ParseInstructionError: failed to parse instruction. \n\n{err}"
-                )
-            }
-        }
-    }
-}
-
-impl error::Error for Error {}
-
-impl From<ParseIntError> for Error {
-    fn from(err: ParseIntError) -> Self {
-        Error::ParseInstructionError(err.to_string())
-    }
-}
-
-pub fn parse_input(input: &str) -> Result<Vec<Instruction>, Error> {
+pub fn parse_input(input: &str) -> ParseResult<Vec<Instruction>> {
     input
         .trim()
         .lines()
         .map(|line| match line.trim() {
             "noop" => Ok(Instruction::Noop),
-            value if value.starts_with("addx") => {
-                let value = value.split(' ').nth(1).ok_or_else(|| {
-                    Error::ParseInstructionError(format!("invalid format `{line}`"))
+            line if line.starts_with("addx") => {
+                let value = line.split(' ').nth(1).ok_or_else(|| {
+                    Diagnostic::at(input, &line[line.len()..], "expected a value after `addx`")
+                })?;
+                let value = value.parse().map_err(|err| {
+                    Diagnostic::at(input, value, format!("invalid value `{value}`: {err}"))
                 })?;
-                let value = value.parse()?;
                 Ok(Instruction::Addx(value))
             }
-            _ => {
-                return Err(Error::ParseInstructionError(format!(
-                    "unrecognized instruction `{line}`"
-                )))
-            }
+            line => Err(Diagnostic::at(
+                input,
+                line,
+                format!("unrecognized instruction `{line}`"),
+            )),
         })
         .collect()
 }
 
-pub fn part1(input: &str) -> Result<i32, Error> {
+pub fn part1(input: &str) -> ParseResult<i32> {
     let input = parse_input(input)?;
 
-    let mut cpu = CPU::new();
+    let mut cpu = Cpu::new();
 
     let sum = cpu.execute(&input);
 
     Ok(sum)
 }
 
-pub fn part2(input: &str) -> Result<String, Error> {
+pub fn part2(input: &str) -> ParseResult<String> {
     let input = parse_input(input)?;
 
-    let mut cpu = CPU::new();
+    let mut cpu = Cpu::new();
 
     let sum = cpu.draw(&input);
 
     Ok(sum)
 }
 
+/// Solution for the Cathode-Ray Tube puzzle
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    fn parse(&self, input: &str) -> Option<Result<(), SolutionError>> {
+        Some(parse_input(input).map(drop).map_err(Into::into))
+    }
+
+    fn part1(&self, input: &str) -> SolutionResult {
+        Ok(Box::new(part1(input)?))
+    }
+
+    fn part2(&self, input: &str) -> SolutionResult {
+        Ok(Box::new(part2(input)?))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use shared_lib::answers::assert_answer;
 
     const BASIC_INPUT: &str = include_str!("../basic_input");
-    const INPUT: &str = include_str!("../input");
+
+    fn input() -> Option<String> {
+        shared_lib::input::personal(env!("CARGO_MANIFEST_DIR"))
+    }
 
     #[test]
     fn basic_input_should_eq_13140() {
@@ -176,8 +179,12 @@ mod tests {
 
     #[test]
     fn input_should_eq_value() {
-        let result = part1(INPUT).unwrap();
-        assert_eq!(result, 14060);
+        let Some(input) = input() else {
+            return;
+        };
+
+        let result = part1(&input).unwrap();
+        assert_answer(10, 1, result);
     }
 
     #[test]
@@ -197,16 +204,18 @@ mod tests {
 
     #[test]
     fn part2_input_should_eq_value() {
-        let result = part2(INPUT).unwrap();
-        assert_eq!(
-            result,
-            " \
-###...##..###..#..#.####.#..#.####...##.
-#..#.#..#.#..#.#.#..#....#.#..#.......#.
-#..#.#..#.#..#.##...###..##...###.....#.
-###..####.###..#.#..#....#.#..#.......#.
-#....#..#.#....#.#..#....#.#..#....#..#.
-#....#..#.#....#..#.#....#..#.####..##.."
-        );
+        let Some(input) = input() else {
+            return;
+        };
+
+        let result = part2(&input).unwrap();
+        assert_answer(10, 2, result);
+    }
+
+    #[test]
+    fn invalid_value_should_point_at_value() {
+        let err = parse_input("noop\naddx 3\naddx five").unwrap_err();
+
+        assert_eq!(err.position(), Some((3, 6)));
     }
 }
@@ -1,42 +1,14 @@
-use std::{collections::VecDeque, error, fmt::Display, num::ParseIntError, str::FromStr};
+use std::{collections::VecDeque, str::FromStr};
+
+use shared_lib::{
+    diagnostic::{Diagnostic, ParseResult},
+    parse,
+    solution::{Solution, SolutionError, SolutionResult},
+};
 
 pub type Index = usize;
 pub type WorryLevel = u64;
 
-#[derive(Debug)]
-pub enum ParseError {
-    Monkey(String),
-    Operation(String),
-    Test(String),
-}
-
-impl Display for ParseError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            ParseError::Monkey(err) => write!(
-                f,
-                "ParseError(Monkey): failed to parse monkey.\n\nCaused by: {err}"
-            ),
-            ParseError::Operation(err) => write!(
-                f,
-                "ParseError(Operation): failed to parse operation.\n\nCaused by: {err}"
-            ),
-            ParseError::Test(err) => write!(
-                f,
-                "ParseError(Test): failed to parse monkey test.\n\nCaused by: {err}"
-            ),
-        }
-    }
-}
-
-impl error::Error for ParseError {}
-
-impl From<ParseIntError> for ParseError {
-    fn from(err: ParseIntError) -> Self {
-        Self::Monkey(err.to_string())
-    }
-}
-
 #[derive(Debug, Clone)]
 pub struct Monkey {
     items: VecDeque<WorryLevel>,
@@ -63,7 +35,7 @@ impl Monkey {
     /// Perform monkey's test on a worry level value and return the
     /// index of the monkey to throw the item to
     fn test(&self, worry_level: WorryLevel) -> Index {
-        if worry_level % self.test.divisor == 0 {
+        if worry_level.is_multiple_of(self.test.divisor) {
             self.test.if_true
         } else {
             self.test.if_false
@@ -72,50 +44,43 @@ impl Monkey {
 }
 
 impl FromStr for Monkey {
-    type Err = ParseError;
+    type Err = Diagnostic;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let value_chunks: Result<Vec<&str>, Self::Err> = s
+    fn from_str(s: &str) -> ParseResult<Self> {
+        let value_chunks = s
             .trim()
             .lines()
             .map(|line| line.trim())
-            .filter(|line| *line != "")
+            .filter(|line| !line.is_empty())
             .skip(1)
-            .map(|line| {
-                let value = line
-                    .split_once(": ")
-                    .ok_or_else(|| {
-                        ParseError::Monkey(format!("Expected deliminator `: ` in `{line}`"))
-                    })?
-                    .1;
-
-                Ok(value)
-            })
-            .collect();
-
-        let value_chunks = value_chunks?;
-
-        let starting_items: Result<VecDeque<WorryLevel>, ParseError> = value_chunks
-            .get(0)
-            .ok_or_else(|| ParseError::Monkey("Missing value chunk at index 0".to_string()))?
-            .split(", ")
-            .map(|worry_level| Ok(worry_level.parse::<WorryLevel>()?))
-            .collect();
-
-        let operation: Operation = value_chunks
-            .get(1)
-            .ok_or_else(|| ParseError::Monkey("Missing value chunk at index 1".to_string()))?
-            .parse()?;
-
-        let test: MonkeyTest = value_chunks
-            .get(2..=4)
-            .ok_or_else(|| {
-                ParseError::Monkey("Missing value chunks in the range 2..=4".to_string())
-            })?
-            .try_into()?;
+            .map(|line| Ok(parse::key_value(s, line)?.1))
+            .collect::<ParseResult<Vec<&str>>>()?;
+
+        let end = &s[s.len()..];
+        let missing = |expected: &str| Diagnostic::at(s, end, format!("expected {expected}"));
+
+        let starting_items = parse::list(
+            value_chunks
+                .first()
+                .ok_or_else(|| missing("starting items"))?,
+            ", ",
+            |worry_level| parse::number(s, worry_level),
+        )?;
+
+        let operation = value_chunks.get(1).ok_or_else(|| missing("an operation"))?;
+        let operation: Operation = operation
+            .parse()
+            .map_err(|err: Diagnostic| err.within(s, operation))?;
+
+        let test = MonkeyTest::parse(
+            s,
+            value_chunks
+                .get(2..=4)
+                .ok_or_else(|| missing("a test with a divisor and two monkeys to throw to"))?,
+        )?;
 
         Ok(Self {
-            items: starting_items?,
+            items: VecDeque::from(starting_items),
             operation,
             test,
             inspection_count: 0,
@@ -133,23 +98,17 @@ struct MonkeyTest {
     if_false: Index,
 }
 
-impl TryFrom<&[&str]> for MonkeyTest {
-    type Error = ParseError;
-
-    fn try_from(parts: &[&str]) -> Result<Self, Self::Error> {
-        let test_parts: Result<Vec<u64>, Self::Error> = parts
-            .into_iter()
+impl MonkeyTest {
+    /// Parse the last number of each of the 3 test `lines`, which are slices
+    /// of `source`
+    fn parse(source: &str, lines: &[&str]) -> ParseResult<Self> {
+        let test_parts = lines
+            .iter()
             .map(|line| {
-                let value: u64 = line
-                    .split(' ')
-                    .last()
-                    .ok_or_else(|| ParseError::Test("Missing a line's last value".to_string()))?
-                    .parse()?;
-                Ok(value)
+                let value = line.rsplit_once(' ').map_or(*line, |(_, value)| value);
+                parse::number(source, value)
             })
-            .collect();
-
-        let test_parts = test_parts?;
+            .collect::<ParseResult<Vec<u64>>>()?;
 
         Ok(MonkeyTest {
             divisor: test_parts[0],
@@ -167,30 +126,29 @@ pub enum Operation {
 }
 
 impl FromStr for Operation {
-    type Err = ParseError;
+    type Err = Diagnostic;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+    fn from_str(s: &str) -> ParseResult<Self> {
         let mut parts = s.split(' ');
+        let end = &s[s.len()..];
 
-        let operator = parts.nth(3).ok_or_else(|| {
-            ParseError::Operation("Missing expected operator (* | +)".to_string())
-        })?;
-        let operand = parts.nth(0).ok_or_else(|| {
-            ParseError::Operation("Missing expected operand (old | integer)".to_string())
-        })?;
+        let operator = parts
+            .nth(3)
+            .ok_or_else(|| Diagnostic::at(s, end, "expected an operator (`*` or `+`)"))?;
+        let operand = parts
+            .next()
+            .ok_or_else(|| Diagnostic::at(s, end, "expected an operand (`old` or an integer)"))?;
 
         let operation = match (operator, operand) {
-            ("+", operand) if operand.chars().all(char::is_numeric) => {
-                Operation::Add(operand.parse()?)
-            }
-            ("*", operand) if operand.chars().all(char::is_numeric) => {
-                Operation::Multiply(operand.parse()?)
-            }
             ("*", "old") => Operation::Square,
-            (operator, operand) => {
-                return Err(ParseError::Operation(format!(
-                    "Invalid operator ({operator}) and/or operand ({operand})"
-                )))
+            ("*", operand) => Operation::Multiply(parse::number(s, operand)?),
+            ("+", operand) => Operation::Add(parse::number(s, operand)?),
+            (operator, _) => {
+                return Err(Diagnostic::at(
+                    s,
+                    operator,
+                    format!("unknown operator `{operator}`, expected `*` or `+`"),
+                ))
             }
         };
 
@@ -198,15 +156,17 @@ impl FromStr for Operation {
     }
 }
 
-fn parse_input(input: &str) -> Result<Vec<Monkey>, ParseError> {
-    input
-        .trim()
-        .split("\n\n")
-        .map(|raw_monkey| -> Result<Monkey, ParseError> { raw_monkey.trim().parse() })
+fn parse_input(input: &str) -> ParseResult<Vec<Monkey>> {
+    parse::blocks(input)
+        .map(|raw_monkey| {
+            raw_monkey
+                .parse::<Monkey>()
+                .map_err(|err| err.within(input, raw_monkey))
+        })
         .collect()
 }
 
-fn process_round(monkeys: &mut Vec<Monkey>) {
+fn process_round(monkeys: &mut [Monkey]) {
     for i in 0..monkeys.len() {
         let mut items = monkeys[i].items.clone();
         monkeys[i].items.clear();
@@ -221,7 +181,7 @@ fn process_round(monkeys: &mut Vec<Monkey>) {
     }
 }
 
-fn process_round_part2(monkeys: &mut Vec<Monkey>) {
+fn process_round_part2(monkeys: &mut [Monkey]) {
     let lcm: u64 = monkeys.iter().map(|monkey| monkey.test.divisor).product();
     for i in 0..monkeys.len() {
         let mut items = monkeys[i].items.clone();
@@ -244,7 +204,7 @@ fn calculate_monkey_business(monkeys: Vec<Monkey>, num_rounds: u64) -> u128 {
         process_round(&mut monkeys)
     }
 
-    monkeys.sort_by(|a, b| b.inspection_count.cmp(&a.inspection_count));
+    monkeys.sort_by_key(|monkey| std::cmp::Reverse(monkey.inspection_count));
 
     monkeys
         .iter()
@@ -260,7 +220,7 @@ fn calculate_monkey_business_part2(monkeys: Vec<Monkey>, num_rounds: u64) -> u12
         process_round_part2(&mut monkeys)
     }
 
-    monkeys.sort_by(|a, b| b.inspection_count.cmp(&a.inspection_count));
+    monkeys.sort_by_key(|monkey| std::cmp::Reverse(monkey.inspection_count));
 
     monkeys
         .iter()
@@ -269,7 +229,7 @@ fn calculate_monkey_business_part2(monkeys: Vec<Monkey>, num_rounds: u64) -> u12
         .product()
 }
 
-pub fn part1(input: &str) -> Result<u128, ParseError> {
+pub fn part1(input: &str) -> ParseResult<u128> {
     let monkeys = parse_input(input)?;
 
     let result = calculate_monkey_business(monkeys, 20);
@@ -277,7 +237,7 @@ pub fn part1(input: &str) -> Result<u128, ParseError> {
     Ok(result)
 }
 
-pub fn part2(input: &str) -> Result<u128, ParseError> {
+pub fn part2(input: &str) -> ParseResult<u128> {
     let monkeys = parse_input(input)?;
 
     let result = calculate_monkey_business_part2(monkeys, 10000);
@@ -285,15 +245,36 @@ pub fn part2(input: &str) -> Result<u128, ParseError> {
     Ok(result)
 }
 
+/// Solution for the Monkey in the Middle puzzle
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    fn parse(&self, input: &str) -> Option<Result<(), SolutionError>> {
+        Some(parse_input(input).map(drop).map_err(Into::into))
+    }
+
+    fn part1(&self, input: &str) -> SolutionResult {
+        Ok(Box::new(part1(input)?))
+    }
+
+    fn part2(&self, input: &str) -> SolutionResult {
+        Ok(Box::new(part2(input)?))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use shared_lib::answers::assert_answer;
 
     const BASIC_INPUT: &str = include_str!("../basic_input");
-    const INPUT: &str = include_str!("../input");
+
+    fn input() -> Option<String> {
+        shared_lib::input::personal(env!("CARGO_MANIFEST_DIR"))
+    }
 
     #[test]
-    fn basic_input_results_in_10605() -> Result<(), ParseError> {
+    fn basic_input_results_in_10605() -> ParseResult<()> {
         let result = part1(BASIC_INPUT)?;
 
         assert_eq!(result, 10605);
@@ -302,16 +283,20 @@ mod tests {
     }
 
     #[test]
-    fn input_results_in_value() -> Result<(), ParseError> {
-        let result = part1(INPUT)?;
+    fn input_results_in_value() -> ParseResult<()> {
+        let Some(input) = input() else {
+            return Ok(());
+        };
+
+        let result = part1(&input)?;
 
-        assert_eq!(result, 61005);
+        assert_answer(11, 1, result);
 
         Ok(())
     }
 
     #[test]
-    fn part2_basic_input_results_in_2_713_310_158() -> Result<(), ParseError> {
+    fn part2_basic_input_results_in_2_713_310_158() -> ParseResult<()> {
         let result = part2(BASIC_INPUT)?;
 
         assert_eq!(result, 2_713_310_158);
@@ -320,11 +305,25 @@ mod tests {
     }
 
     #[test]
-    fn part2_input_results_in_value() -> Result<(), ParseError> {
-        let result = part2(INPUT)?;
+    fn part2_input_results_in_value() -> ParseResult<()> {
+        let Some(input) = input() else {
+            return Ok(());
+        };
+
+        let result = part2(&input)?;
 
-        assert_eq!(result, 20_567_144_694);
+        assert_answer(11, 2, result);
 
         Ok(())
     }
+
+    #[test]
+    fn invalid_operation_should_point_at_operator() {
+        let input = BASIC_INPUT.replacen("old * 19", "old / 19", 1);
+
+        let err = parse_input(&input).unwrap_err();
+
+        assert_eq!(err.position(), Some((3, 24)));
+        assert_eq!(err.message(), "unknown operator `/`, expected `*` or `+`");
+    }
 }
@@ -1,34 +1,17 @@
 use std::{
-    error,
     fmt::{self, Display, Formatter},
-    slice::Iter,
     str::FromStr,
 };
 
 use pathfinding::prelude::dijkstra;
+use shared_lib::{
+    diagnostic::{Diagnostic, ParseResult},
+    grid::{Grid, GridLike},
+    point::Point,
+};
 
 pub type Index = usize;
 
-#[derive(Debug)]
-pub enum ParseError {
-    Graph(String),
-}
-
-impl Display for ParseError {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        match self {
-            ParseError::Graph(value) => {
-                writeln!(
-                    f,
-                    "ParseError(Graph): error parsing tile graph from `{value}`"
-                )
-            }
-        }
-    }
-}
-
-impl error::Error for ParseError {}
-
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum Tile {
     Start,
@@ -52,7 +35,7 @@ impl Tile {
     ///
     /// The elevation is found differently depending on the variant
     /// * [`Tile::Tower`] - this will be its .0 property, which
-    /// is an integer converted from the raw char
+    ///   is an integer converted from the raw char
     /// * [`Tile::Start`] - same elevation as 'a' character
     /// * [`Tile::End`] - same elevation as 'z' character
     fn elevation(&self) -> u32 {
@@ -75,33 +58,25 @@ impl From<char> for Tile {
 }
 
 impl FromStr for Graph {
-    type Err = ParseError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut start: Option<Pos> = None;
-        let mut end: Option<Pos> = None;
-
-        let mut tiles = vec![];
-
-        for (y, line) in s.trim().lines().enumerate() {
-            tiles.push(vec![]);
-
-            for (x, char) in line.trim().chars().enumerate() {
-                let tile = Tile::from(char);
-
-                match tile {
-                    Tile::Start => start = Some(Pos(x, y)),
-                    Tile::End => end = Some(Pos(x, y)),
-                    Tile::Tower(_) => {}
-                };
-
-                tiles[y].push(tile);
-            }
-        }
+    type Err = Diagnostic;
+
+    fn from_str(s: &str) -> ParseResult<Self> {
+        let tiles = Grid::parse_with(s.trim(), |char| match char {
+            'S' | 'E' | 'a'..='z' => Ok(Tile::from(char)),
+            _ => Err("invalid elevation, expected `a`-`z`, `S` or `E`"),
+        })?;
+
+        let find = |target: Tile| {
+            tiles
+                .iter_points()
+                .find(|(_, tile)| **tile == target)
+                .map(|(point, _)| Pos::from(point))
+        };
 
         Ok(Self {
-            start: start.ok_or_else(|| ParseError::Graph(s.to_string()))?,
-            end: end.ok_or_else(|| ParseError::Graph(s.to_string()))?,
+            start: find(Tile::Start)
+                .ok_or_else(|| Diagnostic::new("heightmap has no start `S`"))?,
+            end: find(Tile::End).ok_or_else(|| Diagnostic::new("heightmap has no end `E`"))?,
             tiles,
         })
     }
@@ -111,25 +86,15 @@ impl FromStr for Graph {
 pub struct Graph {
     start: Pos,
     end: Pos,
-    tiles: Vec<Vec<Tile>>,
+    tiles: Grid<Tile>,
 }
 
 impl Graph {
-    fn height(&self) -> usize {
-        self.tiles.len()
-    }
-
-    fn width(&self) -> usize {
-        self.tiles.get(0).map(|row| row.len()).unwrap_or(0)
-    }
-
-    fn get(&self, pos: &Pos) -> Option<&Tile> {
-        self.tiles.get(pos.1)?.get(pos.0)
-    }
-
-    /// Return an iterator over each row of the graph
-    pub fn iter(&self) -> Iter<'_, Vec<Tile>> {
-        self.tiles.iter()
+    /// Return an iterator over each tile of the graph with its position
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &Tile)> {
+        self.tiles
+            .iter_points()
+            .map(|(point, tile)| (Pos::from(point), tile))
     }
 }
 
@@ -149,48 +114,26 @@ impl From<(Index, Index)> for Pos {
     }
 }
 
-impl Pos {
-    pub fn successors(&self, graph: &Graph) -> Vec<(Pos, u32)> {
-        let &Self(x, y) = self;
-
-        let mut positions = vec![];
-
-        let validate_pos = |x: Index, y: Index| -> Option<Pos> {
-            let successor_pos = Pos(x, y);
-
-            let current_tile = graph.get(self).unwrap();
-            let successor_tile = graph.get(&successor_pos).unwrap();
-
-            if successor_tile.elevation() <= current_tile.elevation() + 1 {
-                return Some(successor_pos);
-            }
-
-            None
-        };
-
-        let mut add_pos = |x: Index, y: Index| {
-            if let Some(successor_pos) = validate_pos(x, y) {
-                positions.push((successor_pos, 1));
-            }
-        };
-
-        if x > 0 {
-            add_pos(x - 1, y)
-        }
-
-        if y > 0 {
-            add_pos(x, y - 1)
-        }
-
-        if y < graph.height() - 1 {
-            add_pos(x, y + 1)
-        }
+impl From<Point> for Pos {
+    fn from(Point { x, y }: Point) -> Self {
+        Self(x, y)
+    }
+}
 
-        if x < graph.width() - 1 {
-            add_pos(x + 1, y)
-        }
+impl Pos {
+    fn point(&self) -> Point {
+        Point::from((self.0, self.1))
+    }
 
-        positions
+    pub fn successors(&self, graph: &Graph) -> Vec<(Pos, u32)> {
+        let current_tile = &graph.tiles[self.point()];
+
+        graph
+            .tiles
+            .neighbors4(self.point())
+            .filter(|&point| graph.tiles[point].elevation() <= current_tile.elevation() + 1)
+            .map(|point| (Pos::from(point), 1))
+            .collect()
     }
 }
 
@@ -1,54 +1,68 @@
 mod graph;
 
-use graph::{Graph, ParseError, Pos};
+use shared_lib::{
+    diagnostic::{Diagnostic, ParseResult},
+    solution::{Solution, SolutionError, SolutionResult},
+};
 
-pub fn part1(input: &str) -> Result<usize, ParseError> {
+use graph::{Graph, Pos};
+
+pub fn part1(input: &str) -> ParseResult<usize> {
     let graph: Graph = input.parse()?;
 
-    let result = graph::find_shortest_path_from_start(graph).expect("no path found");
+    let result = graph::find_shortest_path_from_start(graph)
+        .ok_or_else(|| Diagnostic::new("no path leads from the start to the end"))?;
 
     Ok(result.0.len() - 1)
 }
 
-pub fn part2(input: &str) -> Result<usize, ParseError> {
+pub fn part2(input: &str) -> ParseResult<usize> {
     let graph: Graph = input.parse()?;
 
-    let mut min = usize::MAX;
-
     let starting_positions: Vec<Pos> = graph
         .iter()
-        .enumerate()
-        .flat_map(|(y, row)| -> Vec<Pos> {
-            row.iter()
-                .enumerate()
-                .filter_map(|(x, tile)| {
-                    if tile.char() == 'a' {
-                        return Some(Pos(x, y));
-                    }
-                    None
-                })
-                .collect()
-        })
+        .filter(|(_, tile)| tile.char() == 'a')
+        .map(|(pos, _)| pos)
         .collect();
 
-    for pos in starting_positions {
-        if let Some(result) = graph::find_shortest_path(&pos, graph.clone()) {
-            min = min.min(result.0.len() - 1)
-        }
+    starting_positions
+        .iter()
+        .filter_map(|pos| graph::find_shortest_path(pos, graph.clone()))
+        .map(|result| result.0.len() - 1)
+        .min()
+        .ok_or_else(|| Diagnostic::new("no path leads from an `a` square to the end"))
+}
+
+/// Solution for the Hill Climbing Algorithm puzzle
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    fn parse(&self, input: &str) -> Option<Result<(), SolutionError>> {
+        Some(input.parse::<Graph>().map(drop).map_err(Into::into))
+    }
+
+    fn part1(&self, input: &str) -> SolutionResult {
+        Ok(Box::new(part1(input)?))
     }
 
-    Ok(min)
+    fn part2(&self, input: &str) -> SolutionResult {
+        Ok(Box::new(part2(input)?))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use shared_lib::answers::assert_answer;
 
     const BASIC_INPUT: &str = include_str!("../basic_input");
-    const INPUT: &str = include_str!("../input");
+
+    fn input() -> Option<String> {
+        shared_lib::input::personal(env!("CARGO_MANIFEST_DIR"))
+    }
 
     #[test]
-    fn part1_basic_input_eq_31() -> Result<(), ParseError> {
+    fn part1_basic_input_eq_31() -> ParseResult<()> {
         let result = part1(BASIC_INPUT)?;
 
         assert_eq!(result, 31);
@@ -57,16 +71,20 @@ mod tests {
     }
 
     #[test]
-    fn part1_basic_input_eq_value() -> Result<(), ParseError> {
-        let result = part1(INPUT)?;
+    fn part1_basic_input_eq_value() -> ParseResult<()> {
+        let Some(input) = input() else {
+            return Ok(());
+        };
+
+        let result = part1(&input)?;
 
-        assert_eq!(result, 394);
+        assert_answer(12, 1, result);
 
         Ok(())
     }
 
     #[test]
-    fn part2_basic_input_eq_29() -> Result<(), ParseError> {
+    fn part2_basic_input_eq_29() -> ParseResult<()> {
         let result = part2(BASIC_INPUT)?;
 
         assert_eq!(result, 29);
@@ -75,11 +93,28 @@ mod tests {
     }
 
     #[test]
-    fn part2_basic_input_eq_value() -> Result<(), ParseError> {
-        let result = part2(INPUT)?;
+    fn part2_basic_input_eq_value() -> ParseResult<()> {
+        let Some(input) = input() else {
+            return Ok(());
+        };
+
+        let result = part2(&input)?;
 
-        assert_eq!(result, 388);
+        assert_answer(12, 2, result);
 
         Ok(())
     }
+
+    #[test]
+    fn invalid_elevation_should_point_at_tile() {
+        let err = part1("Sabc\nab#E").unwrap_err();
+
+        assert_eq!(err.position(), Some((2, 3)));
+    }
+
+    #[test]
+    fn unreachable_end_should_be_an_error() {
+        assert!(part1("Sbz\nzzE").is_err());
+        assert!(part2("Sbz\nzzE").is_err());
+    }
 }
@@ -1,33 +1,11 @@
-use std::{
-    cmp::Ordering,
-    error::Error,
-    fmt::{self, Display, Formatter},
-};
+use std::cmp::Ordering;
 
 use serde::{Deserialize, Serialize};
-
-#[derive(Debug)]
-pub enum ParseError {
-    Packet(String),
-}
-
-impl Display for ParseError {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        match self {
-            ParseError::Packet(string) => {
-                write!(f, "ParseError(Packet): Error parsing packet:\n\n`{string}`")
-            }
-        }
-    }
-}
-
-impl Error for ParseError {}
-
-impl From<serde_json::Error> for ParseError {
-    fn from(s: serde_json::Error) -> Self {
-        Self::Packet(s.to_string())
-    }
-}
+use shared_lib::{
+    diagnostic::{Diagnostic, ParseResult},
+    parse,
+    solution::{Solution, SolutionError, SolutionResult},
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(untagged)]
@@ -42,12 +20,12 @@ impl Ord for Packet {
         match (self, other) {
             (List(a), List(b)) => a.cmp(b),
             (List(a), Integer(b)) => {
-                let b_list = vec![Integer(b.clone())];
+                let b_list = vec![Integer(*b)];
                 a.cmp(&b_list)
             }
             (Integer(a), List(b)) => {
-                let a_list = vec![Integer(a.clone())];
-                a_list.cmp(&b)
+                let a_list = vec![Integer(*a)];
+                a_list.cmp(b)
             }
             (Integer(a), Integer(b)) => a.cmp(b),
         }
@@ -62,23 +40,34 @@ impl PartialOrd for Packet {
 
 type PacketPair = (Packet, Packet);
 
-fn parse_input(input: &str) -> Result<Vec<PacketPair>, ParseError> {
-    input
-        .trim()
-        .split("\n\n")
-        .map(|line| {
-            let raw_packets = line
-                .split_once("\n")
-                .ok_or_else(|| ParseError::Packet(format!("unable to split {line} by \\n")))?;
-            Ok((
-                serde_json::from_str(raw_packets.0)?,
-                serde_json::from_str(raw_packets.1)?,
-            ))
+/// Parse the packet on `line`, which is a slice of `input`
+fn parse_packet(input: &str, line: &str) -> ParseResult<Packet> {
+    serde_json::from_str(line).map_err(|err| {
+        let offset = err.column().saturating_sub(1).min(line.len());
+        let rest = line.get(offset..).unwrap_or(line);
+        let fragment = &rest[..rest.chars().next().map_or(0, char::len_utf8)];
+
+        // Positions are reported by the diagnostic, relative to the whole input
+        let message = err.to_string();
+        let message = message
+            .rsplit_once(" at line ")
+            .map_or(message.as_str(), |(message, _)| message);
+
+        Diagnostic::at(input, fragment, format!("invalid packet: {message}"))
+    })
+}
+
+fn parse_input(input: &str) -> ParseResult<Vec<PacketPair>> {
+    parse::blocks(input)
+        .map(|pair| {
+            let (left, right) = parse::pair(input, pair, "\n")?;
+
+            Ok((parse_packet(input, left)?, parse_packet(input, right)?))
         })
         .collect()
 }
 
-pub fn part1(input: &str) -> Result<usize, ParseError> {
+pub fn part1(input: &str) -> ParseResult<usize> {
     let packet_pairs = parse_input(input)?;
 
     let sum = packet_pairs
@@ -97,20 +86,14 @@ pub fn part1(input: &str) -> Result<usize, ParseError> {
     Ok(sum)
 }
 
-pub fn part2(input: &str) -> usize {
-    let mut packets: Vec<Packet> = input
+pub fn part2(input: &str) -> ParseResult<usize> {
+    let mut packets = input
         .trim()
         .lines()
-        .filter_map(|line| {
-            let line = line.trim();
-
-            if line.is_empty() {
-                return None;
-            }
-
-            serde_json::from_str(line).ok()
-        })
-        .collect();
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+        .map(|line| parse_packet(input, line))
+        .collect::<ParseResult<Vec<Packet>>>()?;
 
     let divider_packets = (
         Packet::List(vec![Packet::List(vec![Packet::Integer(2)])]),
@@ -133,18 +116,39 @@ pub fn part2(input: &str) -> usize {
             None
         });
 
-    decoder.product()
+    Ok(decoder.product())
+}
+
+/// Solution for the Distress Signal puzzle
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    fn parse(&self, input: &str) -> Option<Result<(), SolutionError>> {
+        Some(parse_input(input).map(drop).map_err(Into::into))
+    }
+
+    fn part1(&self, input: &str) -> SolutionResult {
+        Ok(Box::new(part1(input)?))
+    }
+
+    fn part2(&self, input: &str) -> SolutionResult {
+        Ok(Box::new(part2(input)?))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use shared_lib::answers::assert_answer;
 
     const BASIC_INPUT: &str = include_str!("../basic_input");
-    const INPUT: &str = include_str!("../input");
+
+    fn input() -> Option<String> {
+        shared_lib::input::personal(env!("CARGO_MANIFEST_DIR"))
+    }
 
     #[test]
-    fn part1_basic_input_eq_13() -> Result<(), ParseError> {
+    fn part1_basic_input_eq_13() -> ParseResult<()> {
         let result = part1(BASIC_INPUT)?;
 
         assert_eq!(result, 13);
@@ -153,25 +157,45 @@ mod tests {
     }
 
     #[test]
-    fn part1_basic_input_eq_value() -> Result<(), ParseError> {
-        let result = part1(INPUT)?;
+    fn part1_basic_input_eq_value() -> ParseResult<()> {
+        let Some(input) = input() else {
+            return Ok(());
+        };
 
-        assert_eq!(result, 5905);
+        let result = part1(&input)?;
+
+        assert_answer(13, 1, result);
+
+        Ok(())
+    }
+
+    #[test]
+    fn part2_basic_input_eq_140() -> ParseResult<()> {
+        let result = part2(BASIC_INPUT)?;
+
+        assert_eq!(result, 140);
 
         Ok(())
     }
 
     #[test]
-    fn part2_basic_input_eq_140() {
-        let result = part2(BASIC_INPUT);
+    fn part2_basic_input_eq_value() -> ParseResult<()> {
+        let Some(input) = input() else {
+            return Ok(());
+        };
+
+        let result = part2(&input)?;
 
-        assert_eq!(result, 140)
+        assert_answer(13, 2, result);
+
+        Ok(())
     }
 
     #[test]
-    fn part2_basic_input_eq_value() {
-        let result = part2(INPUT);
+    fn invalid_packet_should_point_at_character() {
+        let err = part1("[1,1]\n[1,x]").unwrap_err();
 
-        assert_eq!(result, 21691);
+        assert_eq!(err.position(), Some((2, 4)));
+        assert!(err.message().starts_with("invalid packet: "));
     }
 }
@@ -1,5 +1,13 @@
+use std::fmt::{self, Display, Formatter};
+
 use pathfinding::prelude::Grid;
 
+use shared_lib::{
+    diagnostic::ParseResult,
+    grid, parse,
+    solution::{Solution, SolutionError, SolutionResult},
+};
+
 #[derive(Debug, Eq, Ord, PartialEq, PartialOrd)]
 pub struct Point {
     x: usize,
@@ -28,46 +36,37 @@ fn sorted<T: Ord, const N: usize>(mut arr: [T; N]) -> [T; N] {
     arr
 }
 
-pub fn parse_input(input: &str) -> Grid {
-    input
-        .trim()
-        .lines()
-        .flat_map(|line| {
-            let mut path = Vec::new();
-
-            let mut points = line.trim().split(" -> ").map(|raw_point| {
-                let (x, y) = raw_point
-                    .split_once(",")
-                    .expect("raw point should contain `,`");
-
-                let x = x.parse().expect("left side should be a valid integer");
-                let y = y.parse().expect("right side should be a valid integer");
-
-                Point::from((x, y))
-            });
-
-            if let Some(mut prev) = points.next() {
-                while let Some(current) = points.next() {
-                    if current.x == prev.x {
-                        let [min, max] = sorted([prev.y, current.y]);
-                        path.extend((min..=max).map(|y| (current.x, y)));
-                    } else {
-                        let [min, max] = sorted([prev.x, current.x]);
-                        path.extend((min..=max).map(|x| (x, current.y)));
-                    }
-                    prev = current;
-                }
+pub fn parse_input(input: &str) -> ParseResult<Grid> {
+    let mut path = Vec::new();
+
+    for line in input.trim().lines() {
+        let points = parse::list(line.trim(), " -> ", |raw_point| {
+            parse::point(input, raw_point).map(Point::from)
+        })?;
+
+        for pair in points.windows(2) {
+            let [prev, current] = pair else {
+                continue;
+            };
+
+            if current.x == prev.x {
+                let [min, max] = sorted([prev.y, current.y]);
+                path.extend((min..=max).map(|y| (current.x, y)));
+            } else {
+                let [min, max] = sorted([prev.x, current.x]);
+                path.extend((min..=max).map(|x| (x, current.y)));
             }
+        }
+    }
 
-            path
-        })
-        .collect()
+    Ok(path.into_iter().collect())
 }
 
 const START: Point = Point { x: 500, y: 0 };
 
-pub fn part1(input: &str) -> usize {
-    let mut grid = parse_input(input);
+/// Pour sand until it starts falling into the abyss, returning how many
+/// units came to rest
+fn pour_into_abyss(grid: &mut Grid) -> usize {
     let mut count = 0usize;
     let mut fallen_through = false;
 
@@ -108,6 +107,64 @@ pub fn part1(input: &str) -> usize {
     count
 }
 
+pub fn part1(input: &str) -> ParseResult<usize> {
+    let mut grid = parse_input(input)?;
+
+    Ok(pour_into_abyss(&mut grid))
+}
+
+/// What fills a tile of the cave
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Material {
+    #[default]
+    Air,
+    Rock,
+    Sand,
+    Source,
+}
+
+impl Display for Material {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let char = match self {
+            Material::Air => '.',
+            Material::Rock => '#',
+            Material::Sand => 'o',
+            Material::Source => '+',
+        };
+
+        write!(f, "{char}")
+    }
+}
+
+/// Draw the part of the `cave` that holds anything, from the top down to its
+/// lowest tile, where `rocks` tells rock and sand apart
+pub fn draw(rocks: &Grid, cave: &Grid) -> grid::Grid<Material> {
+    let (min_x, max_x) = cave
+        .iter()
+        .map(|(x, _)| x)
+        .chain([START.x])
+        .fold((usize::MAX, 0), |(min, max), x| (min.min(x), max.max(x)));
+    let max_y = cave.iter().map(|(_, y)| y).max().unwrap_or_default();
+
+    let mut map = grid::Grid::new(max_x - min_x + 1, max_y + 1);
+
+    for (point, material) in map.iter_points_mut() {
+        let vertex = (min_x + point.x, point.y);
+
+        *material = if rocks.has_vertex(vertex) {
+            Material::Rock
+        } else if cave.has_vertex(vertex) {
+            Material::Sand
+        } else if vertex == START.as_tuple() {
+            Material::Source
+        } else {
+            Material::Air
+        };
+    }
+
+    map
+}
+
 /// Include a floor that spans across the entire Grid width
 ///
 /// * Add 2 rows to the grid
@@ -125,8 +182,8 @@ fn with_floor(mut grid: Grid) -> Grid {
     grid
 }
 
-pub fn part2(input: &str) -> usize {
-    let mut grid = with_floor(parse_input(input));
+pub fn part2(input: &str) -> ParseResult<usize> {
+    let mut grid = with_floor(parse_input(input)?);
     let mut count = 0usize;
 
     'outer: loop {
@@ -158,41 +215,108 @@ pub fn part2(input: &str) -> usize {
         }
     }
 
-    count
+    Ok(count)
+}
+
+/// Solution for the Regolith Reservoir puzzle
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    fn parse(&self, input: &str) -> Option<Result<(), SolutionError>> {
+        Some(parse_input(input).map(drop).map_err(Into::into))
+    }
+
+    fn part1(&self, input: &str) -> SolutionResult {
+        Ok(Box::new(part1(input)?))
+    }
+
+    fn part2(&self, input: &str) -> SolutionResult {
+        Ok(Box::new(part2(input)?))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use shared_lib::answers::assert_answer;
 
     const BASIC_INPUT: &str = include_str!("../basic_input");
-    const INPUT: &str = include_str!("../input");
+
+    fn input() -> Option<String> {
+        shared_lib::input::personal(env!("CARGO_MANIFEST_DIR"))
+    }
 
     #[test]
-    fn part1_basic_input_result_eq_expected() {
-        let result = part1(BASIC_INPUT);
+    fn part1_basic_input_result_eq_expected() -> ParseResult<()> {
+        let result = part1(BASIC_INPUT)?;
 
         assert_eq!(result, 24);
+
+        Ok(())
     }
 
     #[test]
-    fn part1_input_result_eq_expected() {
-        let result = part1(INPUT);
+    fn part1_input_result_eq_expected() -> ParseResult<()> {
+        let Some(input) = input() else {
+            return Ok(());
+        };
+
+        let result = part1(&input)?;
 
-        assert_eq!(result, 817);
+        assert_answer(14, 1, result);
+
+        Ok(())
+    }
+
+    #[test]
+    fn part2_basic_input_result_eq_expected() -> ParseResult<()> {
+        let result = part2(BASIC_INPUT)?;
+
+        assert_eq!(result, 93);
+
+        Ok(())
     }
 
     #[test]
-    fn part2_basic_input_result_eq_expected() {
-        let result = part2(BASIC_INPUT);
+    fn part2_input_result_eq_expected() -> ParseResult<()> {
+        let Some(input) = input() else {
+            return Ok(());
+        };
 
-        assert_eq!(result, 93)
+        let result = part2(&input)?;
+
+        assert_answer(14, 2, result);
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_draw_sand_at_rest() -> ParseResult<()> {
+        let rocks = parse_input(BASIC_INPUT)?;
+        let mut cave = rocks.clone();
+        pour_into_abyss(&mut cave);
+
+        let expected = "\
+......+...
+..........
+......o...
+.....ooo..
+....#ooo##
+...o#ooo#.
+..###ooo#.
+....oooo#.
+.o.ooooo#.
+#########.";
+
+        assert_eq!(draw(&rocks, &cave).to_string(), expected);
+
+        Ok(())
     }
 
     #[test]
-    fn part2_input_result_eq_expected() {
-        let result = part2(INPUT);
+    fn invalid_point_should_point_at_coordinate() {
+        let err = parse_input("498,4 -> 498,6\n503,4 -> 502,y").unwrap_err();
 
-        assert_eq!(result, 23416);
+        assert_eq!(err.position(), Some((2, 14)));
     }
 }
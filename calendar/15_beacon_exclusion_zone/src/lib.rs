@@ -1,15 +1,11 @@
-use std::{
-    cmp::PartialOrd,
-    collections::{HashMap, HashSet},
-    num::ParseIntError,
-    ops::Range,
-};
+use std::{cmp::PartialOrd, collections::HashSet, ops::Range};
 
 use itertools::Itertools;
-use regex::Regex;
-
-#[macro_use]
-extern crate lazy_static;
+use shared_lib::{
+    diagnostic::{Diagnostic, ParseResult},
+    parse,
+    solution::{Solution, SolutionError, SolutionResult},
+};
 
 type Integer = i32;
 
@@ -72,24 +68,15 @@ impl Reading {
     }
 }
 
-/// Extract integers from input into a [`Vec`]
-fn collect_numbers(input: &str) -> Result<Vec<Integer>, ParseIntError> {
-    lazy_static! {
-        static ref RE: Regex = Regex::new(r"-?\d+").unwrap();
-    }
-
-    RE.find_iter(input)
-        .map(|raw| raw.as_str().parse())
-        .collect()
-}
-
 /// Parse sensor reading from one line of input
-fn parse_line(line: &str) -> anyhow::Result<Reading> {
-    let values = collect_numbers(line)?;
+fn parse_line(input: &str, line: &str) -> ParseResult<Reading> {
+    let values: Vec<Integer> = parse::integers(input, line)?;
 
     if values.len() != 4 {
-        return Err(anyhow::anyhow!(
-            "expected input to contain 4 numbers\n\nReceived: `{line}`"
+        return Err(Diagnostic::at(
+            input,
+            line,
+            format!("expected 4 numbers, found {}", values.len()),
         ));
     }
 
@@ -99,8 +86,11 @@ fn parse_line(line: &str) -> anyhow::Result<Reading> {
     ))
 }
 
-fn parse_input(input: &str) -> anyhow::Result<Vec<Reading>> {
-    input.lines().map(|line| parse_line(line.trim())).collect()
+fn parse_input(input: &str) -> ParseResult<Vec<Reading>> {
+    input
+        .lines()
+        .map(|line| parse_line(input, line.trim()))
+        .collect()
 }
 
 type TryMergeRangeResult<Idx> = Result<Range<Idx>, (Range<Idx>, Range<Idx>)>;
@@ -144,8 +134,6 @@ fn count_covered_cells(readings: Vec<Reading>, row_index: Integer) -> usize {
 fn find_beacon<const LOWER_BOUND: Integer, const UPPER_BOUND: Integer>(
     readings: Vec<Reading>,
 ) -> Option<Point> {
-    for row_index in LOWER_BOUND..UPPER_BOUND {}
-
     let possible_cells: HashSet<Point> = (LOWER_BOUND..UPPER_BOUND)
         .flat_map(|y| (LOWER_BOUND..UPPER_BOUND).map(move |x| (x, y).into()))
         .collect();
@@ -168,11 +156,8 @@ fn find_beacon<const LOWER_BOUND: Integer, const UPPER_BOUND: Integer>(
         .cloned()
 }
 
-pub fn part1<const ROW_INDEX: Integer>(input: &str) -> anyhow::Result<usize> {
-    parse_input(input).and_then(|readings| {
-        let count = count_covered_cells(readings, ROW_INDEX);
-        Ok(count)
-    })
+pub fn part1<const ROW_INDEX: Integer>(input: &str) -> ParseResult<usize> {
+    parse_input(input).map(|readings| count_covered_cells(readings, ROW_INDEX))
 }
 
 pub fn part2<const LOWER_BOUND: Integer, const UPPER_BOUND: Integer>(
@@ -187,12 +172,33 @@ pub fn part2<const LOWER_BOUND: Integer, const UPPER_BOUND: Integer>(
     Err(anyhow::anyhow!("beacon not found"))
 }
 
+/// Solution for the Beacon Exclusion Zone puzzle
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    fn parse(&self, input: &str) -> Option<Result<(), SolutionError>> {
+        Some(parse_input(input).map(drop).map_err(Into::into))
+    }
+
+    fn part1(&self, input: &str) -> SolutionResult {
+        Ok(Box::new(part1::<2_000_000>(input)?))
+    }
+
+    fn part2(&self, input: &str) -> SolutionResult {
+        Ok(Box::new(part2::<0, 4_000_000>(input)?))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use shared_lib::answers::assert_answer;
 
     const BASIC_INPUT: &str = include_str!("../basic_input");
-    const INPUT: &str = include_str!("../input");
+
+    fn input() -> Option<String> {
+        shared_lib::input::personal(env!("CARGO_MANIFEST_DIR"))
+    }
 
     #[test]
     fn part1_basic_input_result_eq_expected() -> anyhow::Result<()> {
@@ -205,9 +211,13 @@ mod tests {
 
     #[test]
     fn part1_input_result_eq_expected() -> anyhow::Result<()> {
-        let result = part1::<2_000_000>(INPUT)?;
+        let Some(input) = input() else {
+            return Ok(());
+        };
+
+        let result = part1::<2_000_000>(&input)?;
 
-        assert_eq!(result, 5_838_453);
+        assert_answer(15, 1, result);
 
         Ok(())
     }
@@ -223,10 +233,25 @@ mod tests {
 
     #[test]
     fn part2_input_result_eq_expected() -> anyhow::Result<()> {
-        let result = part2::<0, 4_000_000>(INPUT)?;
+        let Some(input) = input() else {
+            return Ok(());
+        };
+
+        let result = part2::<0, 4_000_000>(&input)?;
 
-        assert_eq!(result, 0);
+        assert_answer(15, 2, result);
 
         Ok(())
     }
+
+    #[test]
+    fn reading_without_beacon_should_point_at_line() {
+        let err = parse_input(
+            "Sensor at x=2, y=18: closest beacon is at x=-2, y=15\nSensor at x=9, y=16",
+        )
+        .unwrap_err();
+
+        assert_eq!(err.position(), Some((2, 1)));
+        assert_eq!(err.message(), "expected 4 numbers, found 2");
+    }
 }
@@ -1,3 +1,18 @@
+use shared_lib::solution::{Solution, SolutionResult};
+
+/// Solution for this day's puzzle
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    fn part1(&self, input: &str) -> SolutionResult {
+        Ok(Box::new(part1(input)))
+    }
+
+    fn part2(&self, input: &str) -> SolutionResult {
+        Ok(Box::new(part2(input)))
+    }
+}
+
 pub fn part1(_input: &str) -> usize {
     todo!()
 }
@@ -11,7 +26,10 @@ mod tests {
     use super::*;
 
     const BASIC_INPUT: &str = include_str!("../basic_input");
-    const INPUT: &str = include_str!("../input");
+
+    fn input() -> Option<String> {
+        shared_lib::input::personal(env!("CARGO_MANIFEST_DIR"))
+    }
 
     #[test]
     fn part1_basic_input_result_eq_expected() {
@@ -22,7 +40,11 @@ mod tests {
 
     #[test]
     fn part1_input_result_eq_expected() {
-        let result = part1(INPUT);
+        let Some(input) = input() else {
+            return;
+        };
+
+        let result = part1(&input);
 
         assert_eq!(result, 0);
     }
@@ -36,7 +58,11 @@ mod tests {
 
     #[test]
     fn part2_input_result_eq_expected() {
-        let result = part2(INPUT);
+        let Some(input) = input() else {
+            return;
+        };
+
+        let result = part2(&input);
 
         assert_eq!(result, 0);
     }
@@ -0,0 +1,266 @@
+//! # Answers
+//!
+//! Module for the store of known puzzle answers, kept in `answers.toml` at the
+//! workspace root so tests and the runner can check solutions against them
+//!
+//! ```toml
+//! year = 2022
+//!
+//! [day.01]
+//! part1 = "72602"
+//! part2 = "207410"
+//! ```
+//!
+//! Answers are always strings since some puzzles have text answers. Day keys
+//! are padded to two digits so the days stay in order when the file is saved.
+
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    fmt::{self, Display, Formatter},
+    fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use serde::{Deserialize, Serialize};
+
+pub type Day = u8;
+
+/// Name of the answer store at the workspace root
+pub const FILE_NAME: &str = "answers.toml";
+
+/// Known answers for every day, and the event year they belong to
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Answers {
+    pub year: Option<u16>,
+    days: BTreeMap<Day, DayAnswers>,
+}
+
+/// Known answers for both parts of a day
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DayAnswers {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part1: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part2: Option<String>,
+}
+
+/// Layout of the file on disk, which can only have string keys
+#[derive(Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AnswersFile {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    year: Option<u16>,
+    #[serde(default)]
+    day: BTreeMap<String, DayAnswers>,
+}
+
+#[derive(Debug)]
+pub enum AnswersError {
+    Io(PathBuf, io::Error),
+    Toml(String),
+    InvalidDay(String),
+    InvalidPart(u8),
+}
+
+impl Display for AnswersError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            AnswersError::Io(path, err) => {
+                write!(
+                    f,
+                    "AnswersError: failed to access `{}`: {err}",
+                    path.display()
+                )
+            }
+            AnswersError::Toml(message) => write!(f, "AnswersError: {message}"),
+            AnswersError::InvalidDay(key) => {
+                write!(f, "AnswersError: `{key}` is not a valid day")
+            }
+            AnswersError::InvalidPart(part) => {
+                write!(f, "AnswersError: part must be 1 or 2, got {part}")
+            }
+        }
+    }
+}
+
+impl Error for AnswersError {}
+
+/// Result of checking an answer against the store
+#[derive(Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail {
+        expected: String,
+    },
+    /// No answer is recorded for the part yet
+    Missing,
+}
+
+impl FromStr for Answers {
+    type Err = AnswersError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let file: AnswersFile =
+            toml::from_str(s).map_err(|err| AnswersError::Toml(err.to_string()))?;
+
+        let days = file
+            .day
+            .into_iter()
+            .map(|(key, answers)| match key.parse() {
+                Ok(day) => Ok((day, answers)),
+                Err(_) => Err(AnswersError::InvalidDay(key)),
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            year: file.year,
+            days,
+        })
+    }
+}
+
+impl Answers {
+    /// Path of the store at the root of this workspace
+    pub fn workspace_path() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .parent()
+            .expect("shared_lib should be inside the workspace")
+            .join(FILE_NAME)
+    }
+
+    /// Read the store at `path`, which is empty if the file does not exist
+    pub fn load(path: &Path) -> Result<Self, AnswersError> {
+        match fs::read_to_string(path) {
+            Ok(text) => text.parse(),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(AnswersError::Io(path.to_path_buf(), err)),
+        }
+    }
+
+    /// Write the store to `path`
+    pub fn save(&self, path: &Path) -> Result<(), AnswersError> {
+        let file = AnswersFile {
+            year: self.year,
+            day: self
+                .days
+                .iter()
+                .map(|(day, answers)| (format!("{day:02}"), answers.clone()))
+                .collect(),
+        };
+
+        let text = toml::to_string(&file).map_err(|err| AnswersError::Toml(err.to_string()))?;
+
+        fs::write(path, text).map_err(|err| AnswersError::Io(path.to_path_buf(), err))
+    }
+
+    /// Recorded answer for one part of a day
+    pub fn get(&self, day: Day, part: u8) -> Option<&str> {
+        let answers = self.days.get(&day)?;
+
+        match part {
+            1 => answers.part1.as_deref(),
+            2 => answers.part2.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Record the answer for one part of a day, replacing any previous one
+    pub fn set(&mut self, day: Day, part: u8, answer: &str) -> Result<(), AnswersError> {
+        let answers = self.days.entry(day).or_default();
+
+        let slot = match part {
+            1 => &mut answers.part1,
+            2 => &mut answers.part2,
+            _ => return Err(AnswersError::InvalidPart(part)),
+        };
+        *slot = Some(answer.to_string());
+
+        Ok(())
+    }
+
+    /// Compare an answer with the recorded one
+    pub fn check(&self, day: Day, part: u8, answer: &str) -> Verdict {
+        match self.get(day, part) {
+            Some(expected) if expected == answer => Verdict::Pass,
+            Some(expected) => Verdict::Fail {
+                expected: expected.to_string(),
+            },
+            None => Verdict::Missing,
+        }
+    }
+}
+
+/// Assert that `answer` matches the workspace store, for use in day crate tests
+///
+/// Parts without a recorded answer are skipped with a note on stderr rather
+/// than failing, so a new day's tests pass until its answers are known.
+pub fn assert_answer(day: Day, part: u8, answer: impl Display) {
+    let answers = Answers::load(&Answers::workspace_path()).expect("answer store should be valid");
+
+    match answers.check(day, part, &answer.to_string()) {
+        Verdict::Pass => {}
+        Verdict::Fail { expected } => {
+            panic!("day {day} part {part}: expected `{expected}`, got `{answer}`")
+        }
+        Verdict::Missing => eprintln!("day {day} part {part}: no recorded answer, skipping"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ANSWERS: &str = r##"
+year = 2022
+
+[day.1]
+part1 = "24000"
+
+[day.10]
+part1 = "13140"
+part2 = "#..#"
+"##;
+
+    #[test]
+    fn should_parse_and_check_answers() {
+        let answers: Answers = ANSWERS.parse().unwrap();
+
+        assert_eq!(answers.year, Some(2022));
+        assert_eq!(answers.check(1, 1, "24000"), Verdict::Pass);
+        assert_eq!(
+            answers.check(10, 1, "1"),
+            Verdict::Fail {
+                expected: "13140".to_string()
+            }
+        );
+        assert_eq!(answers.check(1, 2, "45000"), Verdict::Missing);
+        assert!("[day.first]\npart1 = \"1\"".parse::<Answers>().is_err());
+    }
+
+    #[test]
+    fn should_save_padded_days_in_order() {
+        let path = std::env::temp_dir().join(format!("answers-{}.toml", std::process::id()));
+        let mut answers: Answers = ANSWERS.parse().unwrap();
+        answers.set(2, 1, "15").unwrap();
+        assert!(answers.set(2, 3, "15").is_err());
+
+        answers.save(&path).unwrap();
+        let text = fs::read_to_string(&path).unwrap();
+        let saved = Answers::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        let days: Vec<&str> = text.lines().filter(|line| line.starts_with('[')).collect();
+        assert_eq!(days, ["[day.01]", "[day.02]", "[day.10]"]);
+        assert_eq!(saved, answers);
+    }
+
+    #[test]
+    fn should_load_missing_file_as_empty() {
+        let answers = Answers::load(Path::new("does/not/exist.toml")).unwrap();
+
+        assert_eq!(answers, Answers::default());
+    }
+}
@@ -0,0 +1,199 @@
+//! # Diagnostic
+//!
+//! Module for the error returned by puzzle input parsers. It points at the
+//! part of the input that failed to parse and renders it like a compiler
+//! error:
+//!
+//! ```text
+//! error: unknown direction `X`
+//!  --> 3:1
+//!   |
+//! 3 | X 4
+//!   | ^
+//! ```
+
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+/// Error with a message and, when known, the input it applies to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    message: String,
+    span: Option<Span>,
+}
+
+/// Part of a source text, as a byte offset and length
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Span {
+    source: String,
+    offset: usize,
+    len: usize,
+}
+
+pub type ParseResult<T> = Result<T, Diagnostic>;
+
+impl Diagnostic {
+    /// Diagnostic that does not point at any input
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            span: None,
+        }
+    }
+
+    /// Diagnostic pointing at `fragment` of `source`
+    ///
+    /// `fragment` should be a slice of `source`, like the lines and words
+    /// produced by `str::lines` and `str::split`, so its exact position is
+    /// known. Other strings are looked up by value instead.
+    pub fn at(source: &str, fragment: &str, message: impl Into<String>) -> Self {
+        let span = offset_in(source, fragment).map(|offset| Span {
+            source: source.to_string(),
+            offset,
+            len: fragment.len(),
+        });
+
+        Self {
+            message: message.into(),
+            span,
+        }
+    }
+
+    /// Move a diagnostic for `piece` into the `source` that `piece` is a slice
+    /// of, so that nested parsers report positions in the whole input
+    pub fn within(self, source: &str, piece: &str) -> Self {
+        let Some(span) = &self.span else {
+            return Self::at(source, piece, self.message);
+        };
+
+        match offset_in(source, piece) {
+            Some(piece_offset) => Self {
+                span: Some(Span {
+                    source: source.to_string(),
+                    offset: piece_offset + span.offset,
+                    len: span.len,
+                }),
+                message: self.message,
+            },
+            None => self,
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Line and column the diagnostic points at, both starting from 1
+    pub fn position(&self) -> Option<(usize, usize)> {
+        let span = self.span.as_ref()?;
+        let before = &span.source[..span.offset];
+        let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+
+        Some((
+            before.matches('\n').count() + 1,
+            before[line_start..].chars().count() + 1,
+        ))
+    }
+}
+
+/// Byte offset of `fragment` in `source`
+fn offset_in(source: &str, fragment: &str) -> Option<usize> {
+    let start = source.as_ptr() as usize;
+    let address = fragment.as_ptr() as usize;
+
+    if address >= start && address + fragment.len() <= start + source.len() {
+        Some(address - start)
+    } else {
+        source.find(fragment)
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "error: {}", self.message)?;
+
+        let (Some(span), Some((line, column))) = (&self.span, self.position()) else {
+            return Ok(());
+        };
+
+        let line_start = span.source[..span.offset]
+            .rfind('\n')
+            .map_or(0, |index| index + 1);
+        let text = span.source[line_start..].lines().next().unwrap_or_default();
+
+        let underline_len = span.source[span.offset..]
+            .get(..span.len)
+            .unwrap_or_default()
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .chars()
+            .count()
+            .max(1);
+
+        let gutter = " ".repeat(line.to_string().len());
+
+        writeln!(f)?;
+        writeln!(f, "{gutter}--> {line}:{column}")?;
+        writeln!(f, "{gutter} |")?;
+        writeln!(f, "{line} | {text}")?;
+        write!(
+            f,
+            "{gutter} | {}{}",
+            " ".repeat(column - 1),
+            "^".repeat(underline_len)
+        )
+    }
+}
+
+impl Error for Diagnostic {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = "R 4\nU 4\nX 12\nD 1";
+
+    #[test]
+    fn should_point_at_slice_of_source() {
+        let line = SOURCE.lines().nth(2).unwrap();
+        let (_, amount) = line.split_once(' ').unwrap();
+
+        let diagnostic = Diagnostic::at(SOURCE, amount, "too far");
+
+        assert_eq!(diagnostic.position(), Some((3, 3)));
+        assert_eq!(
+            diagnostic.to_string(),
+            "error: too far\n --> 3:3\n  |\n3 | X 12\n  |   ^^"
+        );
+    }
+
+    #[test]
+    fn should_render_message_without_span() {
+        let diagnostic = Diagnostic::new("input is empty");
+
+        assert_eq!(diagnostic.position(), None);
+        assert_eq!(diagnostic.to_string(), "error: input is empty");
+    }
+
+    #[test]
+    fn should_move_nested_diagnostic_into_source() {
+        let line = SOURCE.lines().nth(2).unwrap();
+        let inner = Diagnostic::at(line, &line[..1], "unknown direction `X`");
+
+        let diagnostic = inner.within(SOURCE, line);
+
+        assert_eq!(diagnostic.position(), Some((3, 1)));
+        assert!(diagnostic.to_string().ends_with("3 | X 12\n  | ^"));
+    }
+
+    #[test]
+    fn should_underline_empty_fragment_with_one_caret() {
+        let diagnostic = Diagnostic::at(SOURCE, &SOURCE[SOURCE.len()..], "expected a line");
+
+        assert_eq!(diagnostic.position(), Some((4, 4)));
+        assert!(diagnostic.to_string().ends_with("4 | D 1\n  |    ^"));
+    }
+}
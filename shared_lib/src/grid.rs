@@ -1,6 +1,7 @@
 //! # Grid
 //!
-//! Module for an implementation of a basic grid container
+//! Module for an implementation of a basic grid container, with any width
+//! and height
 //!
 //! Credit to [this guide](https://blog.adamchalmers.com/grids-1/)
 //! for most of the implementation
@@ -8,11 +9,61 @@
 use std::{
     error::Error,
     fmt::{self, Display, Formatter},
+    iter::{self, Skip, StepBy},
+    ops::{Index, IndexMut, Range},
+    slice::{Iter, IterMut},
     str::FromStr,
     vec::IntoIter,
 };
 
-use crate::point::Point;
+use crate::{diagnostic::Diagnostic, point::Point};
+
+mod view;
+
+pub use view::{Rect, Transform, View};
+
+/// Offsets of the neighbors left, up, right and down of a point
+const OFFSETS4: [(isize, isize); 4] = [(-1, 0), (0, -1), (1, 0), (0, 1)];
+
+/// Offsets of the neighbors of a point, including diagonals, row by row
+const OFFSETS8: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// Direction to look or move in across a grid, where up is towards `y = 0`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+    ];
+
+    /// Change of `x` and `y` for one step in this direction
+    pub fn offset(self) -> (isize, isize) {
+        match self {
+            Direction::Up => (0, -1),
+            Direction::Down => (0, 1),
+            Direction::Left => (-1, 0),
+            Direction::Right => (1, 0),
+        }
+    }
+}
 
 /// Container that stores elements at points across a 2D plane
 pub trait GridLike<T> {
@@ -20,45 +71,145 @@ pub trait GridLike<T> {
     fn height(&self) -> usize;
 
     /// Get the element at a given point
-    fn get(&self, p: Point) -> &T;
+    ///
+    /// # Panics
+    ///
+    /// Panics if the point is outside of the grid, see [`GridLike::try_get`]
+    fn get(&self, p: Point) -> &T {
+        self.try_get(p)
+            .unwrap_or_else(|| panic!("{p} is outside of the grid"))
+    }
+
+    /// Get the element at a given point, or `None` outside of the grid
+    fn try_get(&self, p: Point) -> Option<&T>;
+
+    /// Whether the point is inside of the grid
+    fn contains(&self, p: Point) -> bool {
+        p.x < self.width() && p.y < self.height()
+    }
+
+    /// Points next to `p` to the left, above, to the right and below it,
+    /// skipping the ones outside of the grid
+    fn neighbors4(&self, p: Point) -> impl Iterator<Item = Point> {
+        neighbors(p, &OFFSETS4, self.width(), self.height())
+    }
+
+    /// Points around `p`, including diagonals, skipping the ones outside of
+    /// the grid
+    fn neighbors8(&self, p: Point) -> impl Iterator<Item = Point> {
+        neighbors(p, &OFFSETS8, self.width(), self.height())
+    }
+
+    /// Elements seen looking from `p` in `direction` up to the edge of the
+    /// grid, nearest first and without the element at `p` itself
+    fn ray<'a>(&'a self, p: Point, direction: Direction) -> impl Iterator<Item = &'a T>
+    where
+        T: 'a,
+    {
+        let (dx, dy) = direction.offset();
+        let (width, height) = (self.width(), self.height());
+
+        iter::successors(Some(p), move |&p| step(p, dx, dy, width, height))
+            .skip(1)
+            .map(|p| self.get(p))
+    }
+}
+
+/// [`GridLike`] container whose elements can be changed in place
+pub trait GridLikeMut<T>: GridLike<T> {
+    /// Get the element at a given point mutably, or `None` outside of the grid
+    fn get_mut(&mut self, p: Point) -> Option<&mut T>;
 
     /// Set the element at a given point to value
-    fn set(&mut self, p: Point, value: T);
+    ///
+    /// # Panics
+    ///
+    /// Panics if the point is outside of the grid
+    fn set(&mut self, p: Point, value: T) {
+        *self
+            .get_mut(p)
+            .unwrap_or_else(|| panic!("{p} is outside of the grid")) = value;
+    }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Grid<T> {
     items: Vec<T>,
     width: usize,
     height: usize,
 }
 
-#[derive(Debug)]
-pub struct ParseGridError;
+/// Error for items that do not fit the dimensions of a grid
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseGridError {
+    /// Row at `row` has a different length than the first row
+    Ragged {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// Number of items does not match `width * height`
+    Size {
+        width: usize,
+        height: usize,
+        len: usize,
+    },
+}
 
 impl Display for ParseGridError {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        write!(f, "ParseGridError: failed to parse Grid")
+        match self {
+            ParseGridError::Ragged {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "ParseGridError: row {row} has {found} cells, expected {expected} like the \
+                first row"
+            ),
+            ParseGridError::Size { width, height, len } => write!(
+                f,
+                "ParseGridError: a {width}x{height} grid needs {} items, got {len}",
+                width * height
+            ),
+        }
     }
 }
 
 impl Error for ParseGridError {}
 
-impl<T: FromStr> FromStr for Grid<T> {
-    type Err = ParseGridError;
+impl<T> FromStr for Grid<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    type Err = Diagnostic;
 
+    /// Parse a grid with one character per cell, taking its width from the
+    /// first line
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let items: Result<Vec<_>, _> = s
-            .trim()
-            .lines()
-            .flat_map(|line| {
-                line.split("")
-                    .filter(|val| *val != "")
-                    .map(|val| val.parse::<T>())
-            })
-            .collect();
+        Self::parse_with(s, |char| char.encode_utf8(&mut [0; 4]).parse())
+    }
+}
+
+impl<T> Display for Grid<T>
+where
+    T: Display,
+{
+    /// Write every cell, with each row on its own line
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for y in 0..self.height {
+            if y > 0 {
+                writeln!(f)?;
+            }
 
-        Ok(Self::from(items.map_err(|_| ParseGridError)?))
+            for item in self.row(y) {
+                write!(f, "{item}")?;
+            }
+        }
+
+        Ok(())
     }
 }
 
@@ -75,32 +226,121 @@ where
     }
 }
 
-impl<T> From<Vec<T>> for Grid<T> {
-    fn from(v: Vec<T>) -> Self {
-        let width = (v.len() as f64).sqrt().floor() as usize;
+impl<T> Grid<T> {
+    /// Parse a grid with one character per cell, mapping each of them with
+    /// `parse` and taking the width from the first line
+    pub fn parse_with<E>(
+        input: &str,
+        mut parse: impl FnMut(char) -> Result<T, E>,
+    ) -> Result<Self, Diagnostic>
+    where
+        E: Display,
+    {
+        let mut items = vec![];
+        let mut width = None;
+        let mut height = 0;
 
-        Self {
-            items: v,
-            width,
-            height: width,
+        for line in input.trim_matches(['\n', '\r']).lines() {
+            let expected = *width.get_or_insert(line.chars().count());
+            let found = line.chars().count();
+
+            if found != expected {
+                return Err(Diagnostic::at(
+                    input,
+                    line,
+                    format!("row has {found} cells, expected {expected} like the first row"),
+                ));
+            }
+
+            for (index, char) in line.char_indices() {
+                let item = parse(char).map_err(|err| {
+                    let cell = &line[index..index + char.len_utf8()];
+                    Diagnostic::at(input, cell, format!("invalid grid cell `{cell}`: {err}"))
+                })?;
+
+                items.push(item);
+            }
+
+            height += 1;
         }
+
+        Ok(Self {
+            items,
+            width: width.unwrap_or_default(),
+            height,
+        })
     }
-}
 
-impl<T> FromIterator<T> for Grid<T> {
-    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
-        let items: Vec<T> = iter.into_iter().collect();
+    /// Grid of `width` by `height` items, stored row by row
+    pub fn from_vec(width: usize, height: usize, items: Vec<T>) -> Result<Self, ParseGridError> {
+        if items.len() != width * height {
+            return Err(ParseGridError::Size {
+                width,
+                height,
+                len: items.len(),
+            });
+        }
+
+        Ok(Self {
+            items,
+            width,
+            height,
+        })
+    }
+
+    /// Grid from rows of the same length
+    pub fn from_rows<R>(rows: impl IntoIterator<Item = R>) -> Result<Self, ParseGridError>
+    where
+        R: IntoIterator<Item = T>,
+    {
+        let mut items = vec![];
+        let mut width = None;
+        let mut height = 0;
+
+        for (row, cells) in rows.into_iter().enumerate() {
+            let start = items.len();
+            items.extend(cells);
+
+            let found = items.len() - start;
+            let expected = *width.get_or_insert(found);
+
+            if found != expected {
+                return Err(ParseGridError::Ragged {
+                    row,
+                    expected,
+                    found,
+                });
+            }
 
-        let size = (items.len() as f64).sqrt().floor() as usize;
+            height += 1;
+        }
+
+        Ok(Self {
+            items,
+            width: width.unwrap_or_default(),
+            height,
+        })
+    }
+}
 
+/// Single row grid with every item of the vector
+impl<T> From<Vec<T>> for Grid<T> {
+    fn from(items: Vec<T>) -> Self {
         Self {
+            width: items.len(),
+            height: usize::from(!items.is_empty()),
             items,
-            width: size,
-            height: size,
         }
     }
 }
 
+/// Single row grid with every item of the iterator
+impl<T> FromIterator<T> for Grid<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::from(iter.into_iter().collect::<Vec<T>>())
+    }
+}
+
 impl<T> IntoIterator for Grid<T> {
     type Item = T;
     type IntoIter = IntoIter<T>;
@@ -119,11 +359,335 @@ impl<T> GridLike<T> for Grid<T> {
         self.height
     }
 
-    fn get(&self, p: Point) -> &T {
-        &self.items[p.y * self.width + p.x]
+    fn try_get(&self, p: Point) -> Option<&T> {
+        let index = self.index_of(p)?;
+        self.items.get(index)
+    }
+}
+
+impl<T> GridLikeMut<T> for Grid<T> {
+    fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        let index = self.index_of(p)?;
+        self.items.get_mut(index)
+    }
+}
+
+impl<T> Grid<T> {
+    /// Index of a point in `items`, or `None` outside of the grid
+    fn index_of(&self, p: Point) -> Option<usize> {
+        self.contains(p).then(|| p.y * self.width + p.x)
+    }
+
+    /// Point at an index of `items` in a grid `width` cells wide, taking the
+    /// width instead of `&self` so it can be used while `items` is borrowed
+    fn point_of(width: usize, index: usize) -> Point {
+        Point {
+            x: index % width,
+            y: index / width,
+        }
+    }
+
+    /// Every point of the grid, row by row
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+
+        (0..self.items.len()).map(move |index| Self::point_of(width, index))
+    }
+
+    /// Every element with its point, row by row
+    pub fn iter_points(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| (Self::point_of(self.width, index), item))
     }
 
-    fn set(&mut self, p: Point, v: T) {
-        self.items[p.y * self.width + p.x] = v;
+    /// Every element with its point, row by row, mutably
+    pub fn iter_points_mut(&mut self) -> impl Iterator<Item = (Point, &mut T)> {
+        let width = self.width;
+
+        self.items
+            .iter_mut()
+            .enumerate()
+            .map(move |(index, item)| (Self::point_of(width, index), item))
+    }
+
+    /// Same as [`Grid::iter_points`], named after [`Iterator::enumerate`]
+    pub fn enumerate(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.iter_points()
+    }
+
+    /// Elements of the row at `y`, from left to right
+    ///
+    /// # Panics
+    ///
+    /// Panics if `y` is outside of the grid
+    pub fn row(&self, y: usize) -> Iter<'_, T> {
+        self.items[self.row_range(y)].iter()
+    }
+
+    /// Elements of the row at `y`, from left to right, mutably
+    ///
+    /// # Panics
+    ///
+    /// Panics if `y` is outside of the grid
+    pub fn row_mut(&mut self, y: usize) -> IterMut<'_, T> {
+        let range = self.row_range(y);
+        self.items[range].iter_mut()
+    }
+
+    /// Elements of the column at `x`, from top to bottom
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x` is outside of the grid
+    pub fn col(&self, x: usize) -> StepBy<Skip<Iter<'_, T>>> {
+        assert!(x < self.width, "column {x} is outside of the grid");
+        self.items.iter().skip(x).step_by(self.width)
+    }
+
+    /// Elements of the column at `x`, from top to bottom, mutably
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x` is outside of the grid
+    pub fn col_mut(&mut self, x: usize) -> StepBy<Skip<IterMut<'_, T>>> {
+        assert!(x < self.width, "column {x} is outside of the grid");
+        self.items.iter_mut().skip(x).step_by(self.width)
+    }
+
+    /// Draw the grid with one character per cell, with each row on its own
+    /// line
+    pub fn render_with(&self, mut render: impl FnMut(&T) -> char) -> String {
+        let mut out = String::with_capacity((self.width + 1) * self.height);
+
+        for y in 0..self.height {
+            if y > 0 {
+                out.push('\n');
+            }
+
+            out.extend(self.row(y).map(&mut render));
+        }
+
+        out
+    }
+
+    fn row_range(&self, y: usize) -> Range<usize> {
+        assert!(y < self.height, "row {y} is outside of the grid");
+        y * self.width..(y + 1) * self.width
+    }
+}
+
+/// Points at `offsets` from `p` that are inside of a `width` by `height` grid
+fn neighbors(
+    p: Point,
+    offsets: &'static [(isize, isize)],
+    width: usize,
+    height: usize,
+) -> impl Iterator<Item = Point> {
+    offsets
+        .iter()
+        .filter_map(move |&(dx, dy)| step(p, dx, dy, width, height))
+}
+
+/// Point at `dx` and `dy` from `p`, if it is inside of a `width` by `height`
+/// grid
+fn step(p: Point, dx: isize, dy: isize, width: usize, height: usize) -> Option<Point> {
+    let x = p.x.checked_add_signed(dx).filter(|&x| x < width)?;
+    let y = p.y.checked_add_signed(dy).filter(|&y| y < height)?;
+
+    Some(Point { x, y })
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point) -> &T {
+        self.get(p)
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, p: Point) -> &mut T {
+        self.get_mut(p)
+            .unwrap_or_else(|| panic!("{p} is outside of the grid"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_rectangular_grid() -> Result<(), Diagnostic> {
+        let grid: Grid<u8> = "123\n456\n".parse()?;
+
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(*grid.get(Point { x: 2, y: 1 }), 6);
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_point_at_ragged_row() {
+        let err = "123\n45\n789".parse::<Grid<u8>>().unwrap_err();
+
+        assert_eq!(err.position(), Some((2, 1)));
+        assert_eq!(
+            err.message(),
+            "row has 2 cells, expected 3 like the first row"
+        );
+    }
+
+    #[test]
+    fn should_check_dimensions() {
+        let grid = Grid::from_rows([vec![1, 2, 3], vec![4, 5, 6]]).unwrap();
+
+        assert_eq!(grid, Grid::from_vec(3, 2, vec![1, 2, 3, 4, 5, 6]).unwrap());
+        assert_eq!(
+            Grid::from_rows([vec![1, 2], vec![3]]),
+            Err(ParseGridError::Ragged {
+                row: 1,
+                expected: 2,
+                found: 1
+            })
+        );
+        assert_eq!(
+            Grid::from_vec(2, 2, vec![1, 2, 3]),
+            Err(ParseGridError::Size {
+                width: 2,
+                height: 2,
+                len: 3
+            })
+        );
+    }
+
+    #[test]
+    fn should_collect_into_single_row() {
+        let grid: Grid<u8> = (1..=3).collect();
+
+        assert_eq!((grid.width(), grid.height()), (3, 1));
+        assert_eq!(grid, Grid::from(vec![1, 2, 3]));
+        assert_eq!(Grid::<u8>::from(vec![]).height(), 0);
+    }
+
+    #[test]
+    fn should_describe_errors() {
+        let err = Grid::from_vec(2, 2, vec![1, 2, 3]).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "ParseGridError: a 2x2 grid needs 4 items, got 3"
+        );
+    }
+
+    #[test]
+    fn should_check_bounds() {
+        let mut grid = Grid::from_vec(3, 2, vec![1, 2, 3, 4, 5, 6]).unwrap();
+
+        assert_eq!(grid.try_get(Point { x: 3, y: 0 }), None);
+        assert_eq!(grid.get_mut(Point { x: 0, y: 2 }), None);
+
+        grid[Point { x: 2, y: 1 }] += 10;
+
+        assert_eq!(grid[Point { x: 2, y: 1 }], 16);
+    }
+
+    #[test]
+    #[should_panic(expected = "Point(3, 0) is outside of the grid")]
+    fn should_not_wrap_to_next_row() {
+        let grid = Grid::from_vec(3, 2, vec![1, 2, 3, 4, 5, 6]).unwrap();
+
+        let _ = grid[Point { x: 3, y: 0 }];
+    }
+
+    #[test]
+    fn should_find_neighbors_inside_grid() {
+        let grid: Grid<u8> = Grid::new(3, 2);
+
+        let corner: Vec<Point> = grid.neighbors4(Point { x: 0, y: 0 }).collect();
+        let middle = grid.neighbors8(Point { x: 1, y: 1 }).count();
+
+        assert_eq!(corner, [Point { x: 1, y: 0 }, Point { x: 0, y: 1 }]);
+        assert_eq!(middle, 5);
+    }
+
+    #[test]
+    fn should_iterate_with_points() {
+        let grid = Grid::from_vec(2, 2, vec!['a', 'b', 'c', 'd']).unwrap();
+
+        let (point, item) = grid.iter_points().nth(2).unwrap();
+
+        assert_eq!((point, *item), (Point { x: 0, y: 1 }, 'c'));
+        assert_eq!(grid.points().last(), Some(Point { x: 1, y: 1 }));
+    }
+
+    #[test]
+    fn should_view_rows_and_columns_from_both_ends() {
+        let mut grid = Grid::from_vec(3, 2, vec![1, 2, 3, 4, 5, 6]).unwrap();
+
+        assert_eq!(grid.row(1).rev().collect::<Vec<_>>(), [&6, &5, &4]);
+        assert_eq!(grid.col(2).rev().collect::<Vec<_>>(), [&6, &3]);
+
+        grid.row_mut(0).for_each(|item| *item *= 10);
+        grid.col_mut(0).for_each(|item| *item += 1);
+
+        assert_eq!(
+            grid,
+            Grid::from_vec(3, 2, vec![11, 20, 30, 5, 5, 6]).unwrap()
+        );
+    }
+
+    #[test]
+    fn should_cast_rays_to_the_edge() -> Result<(), Diagnostic> {
+        let grid: Grid<u8> = "123\n456\n789".parse()?;
+        let center = Point { x: 1, y: 1 };
+
+        let ray = |direction| grid.ray(center, direction).copied().collect::<Vec<u8>>();
+
+        assert_eq!(ray(Direction::Up), [2]);
+        assert_eq!(ray(Direction::Left), [4]);
+        assert_eq!(
+            grid.ray(Point { x: 2, y: 2 }, Direction::Left)
+                .collect::<Vec<_>>(),
+            [&8, &7]
+        );
+        assert_eq!(grid.ray(Point { x: 2, y: 2 }, Direction::Down).count(), 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_parse_cells_with_char_mapping() -> Result<(), Diagnostic> {
+        let input = "#..\n.#.";
+        let grid = Grid::parse_with(input, |char| match char {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            _ => Err("expected `#` or `.`"),
+        })?;
+
+        assert_eq!(grid.render_with(|&lit| if lit { '#' } else { '.' }), input);
+
+        let err = Grid::<bool>::parse_with("#.\n.x", |char| {
+            (char == '#' || char == '.')
+                .then_some(char == '#')
+                .ok_or("expected `#` or `.`")
+        })
+        .unwrap_err();
+
+        assert_eq!(err.position(), Some((2, 2)));
+        assert_eq!(err.message(), "invalid grid cell `x`: expected `#` or `.`");
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_display_rows_on_separate_lines() -> Result<(), Diagnostic> {
+        let grid: Grid<u8> = "123\n456\n".parse()?;
+
+        assert_eq!(grid.to_string(), "123\n456");
+        assert_eq!(Grid::<u8>::from_vec(0, 0, vec![]).unwrap().to_string(), "");
+
+        Ok(())
     }
 }
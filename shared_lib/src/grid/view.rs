@@ -0,0 +1,370 @@
+//! # View
+//!
+//! Module for lazy, read-only views of a [`Grid`] after a geometric
+//! transform. A view maps each of its points back to the grid, so nothing is
+//! copied until [`View::to_grid`] is called, and views of views compose into
+//! a single mapping.
+
+use std::ops::Index;
+
+use super::{Grid, GridLike};
+use crate::point::Point;
+
+/// Rectangle of points, starting at `origin` in the top left corner
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rect {
+    pub origin: Point,
+    pub width: usize,
+    pub height: usize,
+}
+
+/// Geometric transform of a grid, see [`Grid::view`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transform {
+    /// Swap rows and columns
+    Transpose,
+    /// Rotate a quarter turn clockwise
+    RotateCw,
+    /// Rotate a quarter turn counterclockwise
+    RotateCcw,
+    /// Mirror left to right
+    FlipHorizontal,
+    /// Mirror top to bottom
+    FlipVertical,
+    /// Part of the grid inside of a rectangle
+    Sub(Rect),
+}
+
+/// Grid as seen through one or more [`Transform`]s
+///
+/// The grid point shown at `(x, y)` is `origin + x * dx + y * dy`, which
+/// covers every transform and any composition of them.
+#[derive(Debug)]
+pub struct View<'a, T> {
+    grid: &'a Grid<T>,
+    width: usize,
+    height: usize,
+    /// Grid point shown at the top left corner of the view
+    origin: (isize, isize),
+    /// Step across the grid for one step to the right in the view
+    dx: (isize, isize),
+    /// Step across the grid for one step down in the view
+    dy: (isize, isize),
+}
+
+impl Rect {
+    pub fn new(origin: Point, width: usize, height: usize) -> Self {
+        Self {
+            origin,
+            width,
+            height,
+        }
+    }
+
+    /// Whether the rectangle fits inside of a `width` by `height` grid
+    fn fits(&self, width: usize, height: usize) -> bool {
+        self.origin.x + self.width <= width && self.origin.y + self.height <= height
+    }
+}
+
+impl<'a, T> View<'a, T> {
+    /// View of the whole grid as it is
+    fn of(grid: &'a Grid<T>) -> Self {
+        Self {
+            grid,
+            width: grid.width(),
+            height: grid.height(),
+            origin: (0, 0),
+            dx: (1, 0),
+            dy: (0, 1),
+        }
+    }
+
+    /// Lazy view of this view through `transform`
+    ///
+    /// # Panics
+    ///
+    /// Panics if a [`Transform::Sub`] rectangle does not fit inside of the
+    /// view
+    pub fn view(&self, transform: Transform) -> Self {
+        let (w, h) = (self.width as isize, self.height as isize);
+
+        // Where the new view starts and steps, in points of this view
+        let (width, height, origin, dx, dy) = match transform {
+            Transform::Transpose => (self.height, self.width, (0, 0), (0, 1), (1, 0)),
+            Transform::RotateCw => (self.height, self.width, (0, h - 1), (0, -1), (1, 0)),
+            Transform::RotateCcw => (self.height, self.width, (w - 1, 0), (0, 1), (-1, 0)),
+            Transform::FlipHorizontal => (self.width, self.height, (w - 1, 0), (-1, 0), (0, 1)),
+            Transform::FlipVertical => (self.width, self.height, (0, h - 1), (1, 0), (0, -1)),
+            Transform::Sub(rect) => {
+                assert!(
+                    rect.fits(self.width, self.height),
+                    "{rect:?} does not fit inside of a {}x{} grid",
+                    self.width,
+                    self.height
+                );
+
+                let origin = (rect.origin.x as isize, rect.origin.y as isize);
+                (rect.width, rect.height, origin, (1, 0), (0, 1))
+            }
+        };
+
+        Self {
+            grid: self.grid,
+            width,
+            height,
+            origin: self.map(origin),
+            dx: self.step(dx),
+            dy: self.step(dy),
+        }
+    }
+
+    /// Grid coordinates shown at `(x, y)`, which may be outside of the grid
+    /// when `(x, y)` is outside of the view
+    fn map(&self, (x, y): (isize, isize)) -> (isize, isize) {
+        let (dx, dy) = self.step((x, y));
+        (self.origin.0 + dx, self.origin.1 + dy)
+    }
+
+    /// Step across the grid for a step of `(x, y)` in the view
+    fn step(&self, (x, y): (isize, isize)) -> (isize, isize) {
+        (x * self.dx.0 + y * self.dy.0, x * self.dx.1 + y * self.dy.1)
+    }
+
+    /// Grid point shown at a point inside of the view
+    fn source(&self, p: Point) -> Point {
+        let (x, y) = self.map((p.x as isize, p.y as isize));
+
+        Point {
+            x: x as usize,
+            y: y as usize,
+        }
+    }
+
+    /// Every element with its point in the view, row by row
+    pub fn iter_points(&self) -> impl Iterator<Item = (Point, &'a T)> {
+        let view = *self;
+
+        (0..self.height)
+            .flat_map(move |y| (0..view.width).map(move |x| Point { x, y }))
+            .map(move |point| (point, &view.grid[view.source(point)]))
+    }
+
+    /// Copy the view into a new grid
+    pub fn to_grid(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            items: self.iter_points().map(|(_, item)| item.clone()).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
+impl<T> GridLike<T> for View<'_, T> {
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn try_get(&self, p: Point) -> Option<&T> {
+        self.contains(p).then(|| &self.grid[self.source(p)])
+    }
+}
+
+// Not derived, as that would require `T: Copy`
+impl<T> Clone for View<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for View<'_, T> {}
+
+impl<T> Index<Point> for View<'_, T> {
+    type Output = T;
+
+    fn index(&self, p: Point) -> &T {
+        self.try_get(p)
+            .unwrap_or_else(|| panic!("{p} is outside of the view"))
+    }
+}
+
+impl<T> Grid<T> {
+    /// Lazy view of the grid through `transform`
+    ///
+    /// # Panics
+    ///
+    /// Panics if a [`Transform::Sub`] rectangle does not fit inside of the grid
+    pub fn view(&self, transform: Transform) -> View<'_, T> {
+        View::of(self).view(transform)
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// New grid with rows and columns swapped
+    pub fn transpose(&self) -> Self {
+        self.view(Transform::Transpose).to_grid()
+    }
+
+    /// New grid rotated a quarter turn clockwise
+    pub fn rotate_cw(&self) -> Self {
+        self.view(Transform::RotateCw).to_grid()
+    }
+
+    /// New grid rotated a quarter turn counterclockwise
+    pub fn rotate_ccw(&self) -> Self {
+        self.view(Transform::RotateCcw).to_grid()
+    }
+
+    /// New grid mirrored left to right
+    pub fn flip_horizontal(&self) -> Self {
+        self.view(Transform::FlipHorizontal).to_grid()
+    }
+
+    /// New grid mirrored top to bottom
+    pub fn flip_vertical(&self) -> Self {
+        self.view(Transform::FlipVertical).to_grid()
+    }
+
+    /// New grid with the part of this one inside of `rect`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rect` does not fit inside of the grid
+    pub fn sub_grid(&self, rect: Rect) -> Self {
+        self.view(Transform::Sub(rect)).to_grid()
+    }
+
+    /// New grid with `n` cells of `fill` added on every side
+    pub fn pad(&self, n: usize, fill: T) -> Self {
+        let width = self.width + 2 * n;
+        let height = self.height + 2 * n;
+
+        let items = (0..width * height)
+            .map(|index| {
+                let (x, y) = (index % width, index / width);
+
+                x.checked_sub(n)
+                    .zip(y.checked_sub(n))
+                    .and_then(|(x, y)| self.try_get(Point { x, y }))
+                    .unwrap_or(&fill)
+                    .clone()
+            })
+            .collect();
+
+        Self {
+            items,
+            width,
+            height,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Direction;
+
+    fn grid() -> Grid<u8> {
+        // 1 2 3
+        // 4 5 6
+        Grid::from_vec(3, 2, vec![1, 2, 3, 4, 5, 6]).unwrap()
+    }
+
+    fn rows(grid: &Grid<u8>) -> Vec<Vec<u8>> {
+        (0..grid.height())
+            .map(|y| grid.row(y).copied().collect())
+            .collect()
+    }
+
+    #[test]
+    fn should_rotate_and_transpose() {
+        assert_eq!(rows(&grid().transpose()), [[1, 4], [2, 5], [3, 6]]);
+        assert_eq!(rows(&grid().rotate_cw()), [[4, 1], [5, 2], [6, 3]]);
+        assert_eq!(rows(&grid().rotate_ccw()), [[3, 6], [2, 5], [1, 4]]);
+        assert_eq!(grid().rotate_cw().rotate_ccw(), grid());
+    }
+
+    #[test]
+    fn should_flip() {
+        assert_eq!(rows(&grid().flip_horizontal()), [[3, 2, 1], [6, 5, 4]]);
+        assert_eq!(rows(&grid().flip_vertical()), [[4, 5, 6], [1, 2, 3]]);
+    }
+
+    #[test]
+    fn should_cut_and_pad() {
+        let rect = Rect::new(Point { x: 1, y: 0 }, 2, 2);
+
+        assert_eq!(rows(&grid().sub_grid(rect)), [[2, 3], [5, 6]]);
+        assert_eq!(
+            rows(&grid().pad(1, 0)),
+            [
+                [0, 0, 0, 0, 0],
+                [0, 1, 2, 3, 0],
+                [0, 4, 5, 6, 0],
+                [0, 0, 0, 0, 0]
+            ]
+        );
+    }
+
+    #[test]
+    fn should_read_through_view_lazily() {
+        let grid = grid();
+        let view = grid.view(Transform::RotateCw);
+
+        assert_eq!((view.width(), view.height()), (2, 3));
+        assert_eq!(view[Point { x: 0, y: 2 }], 6);
+        assert_eq!(view.try_get(Point { x: 2, y: 0 }), None);
+    }
+
+    #[test]
+    fn should_compose_views() {
+        let grid = grid();
+        let view = grid.view(Transform::RotateCw).view(Transform::FlipVertical);
+
+        assert_eq!(rows(&view.to_grid()), [[6, 3], [5, 2], [4, 1]]);
+        assert_eq!(
+            rows(
+                &view
+                    .view(Transform::Sub(Rect::new(Point { x: 1, y: 1 }, 1, 2)))
+                    .to_grid()
+            ),
+            [[2], [1]]
+        );
+        assert_eq!(
+            grid.view(Transform::RotateCw)
+                .view(Transform::RotateCcw)
+                .to_grid(),
+            grid
+        );
+    }
+
+    #[test]
+    fn should_work_as_grid_like() {
+        let grid = grid();
+        let view = grid.view(Transform::Transpose);
+        let corner = Point { x: 0, y: 0 };
+
+        assert_eq!(
+            view.neighbors4(corner).collect::<Vec<_>>(),
+            [Point { x: 1, y: 0 }, Point { x: 0, y: 1 }]
+        );
+        assert_eq!(
+            view.ray(corner, Direction::Down).collect::<Vec<_>>(),
+            [&2, &3]
+        );
+        assert!(!view.contains(Point { x: 0, y: 3 }));
+    }
+
+    #[test]
+    #[should_panic(expected = "does not fit inside of a 3x2 grid")]
+    fn should_reject_rect_outside_of_grid() {
+        grid().view(Transform::Sub(Rect::new(Point { x: 2, y: 0 }, 2, 1)));
+    }
+}
@@ -0,0 +1,202 @@
+//! # Input
+//!
+//! Module for loading puzzle input at runtime. Personal inputs are not meant
+//! to be committed, so nothing should require them to exist at compile time.
+//!
+//! Input is resolved in this order:
+//! 1. a path given on the command line, where `-` means stdin
+//! 2. the path in the [`INPUT_VAR`] environment variable, where `-` also
+//!    means stdin
+//! 3. the day crate's default `input` file
+
+use std::{
+    env,
+    error::Error,
+    fmt::{self, Display, Formatter},
+    fs,
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+    sync::Once,
+};
+
+/// Environment variable with the path of the input to use
+pub const INPUT_VAR: &str = "AOC_INPUT";
+
+/// Name of the personal input file in each day crate
+pub const INPUT_FILE: &str = "input";
+
+/// Where puzzle input is read from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    Stdin,
+    File(PathBuf),
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    Stdin(io::Error),
+    File(PathBuf, io::Error),
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::Stdin(err) => write!(f, "InputError: failed to read stdin: {err}"),
+            InputError::File(path, err) if err.kind() == io::ErrorKind::NotFound => write!(
+                f,
+                "InputError: no puzzle input at `{}`; download it with `getaoc` or \
+                pass another path",
+                path.display()
+            ),
+            InputError::File(path, err) => {
+                write!(f, "InputError: failed to read `{}`: {err}", path.display())
+            }
+        }
+    }
+}
+
+impl Error for InputError {}
+
+impl From<&Path> for Source {
+    /// Source for a path, where `-` means stdin
+    fn from(path: &Path) -> Self {
+        if path == Path::new("-") {
+            Source::Stdin
+        } else {
+            Source::File(path.to_path_buf())
+        }
+    }
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::Stdin => write!(f, "stdin"),
+            Source::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+impl Source {
+    /// Pick the source from a command-line `path`, then [`INPUT_VAR`], then
+    /// the `default` file
+    pub fn resolve(path: Option<&Path>, default: &Path) -> Self {
+        Self::explicit(path).unwrap_or_else(|| Source::File(default.to_path_buf()))
+    }
+
+    /// Source picked by a command-line `path` or [`INPUT_VAR`], if either is
+    /// set, so callers only look up their default file when it is needed
+    pub fn explicit(path: Option<&Path>) -> Option<Self> {
+        if let Some(path) = path {
+            return Some(Source::from(path));
+        }
+
+        match env::var_os(INPUT_VAR) {
+            Some(path) if !path.is_empty() => Some(Source::from(Path::new(&path))),
+            _ => None,
+        }
+    }
+
+    pub fn read(&self) -> Result<String, InputError> {
+        match self {
+            Source::Stdin => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .map_err(InputError::Stdin)?;
+                Ok(input)
+            }
+            Source::File(path) => {
+                fs::read_to_string(path).map_err(|err| InputError::File(path.clone(), err))
+            }
+        }
+    }
+}
+
+/// Resolve and read the input, see [`Source::resolve`]
+pub fn load(path: Option<&Path>, default: &Path) -> Result<String, InputError> {
+    Source::resolve(path, default).read()
+}
+
+/// Personal input of the day crate at `manifest_dir`, for use in tests
+///
+/// Returns `None` when the input has not been downloaded, so tests can skip
+/// instead of failing on a fresh clone. A note is written straight to stderr
+/// once per test run, as the test harness hides `eprintln!` from passing
+/// tests:
+///
+/// ```no_run
+/// # fn part1(_: &str) -> usize { 0 }
+/// let Some(input) = shared_lib::input::personal(env!("CARGO_MANIFEST_DIR")) else {
+///     return;
+/// };
+///
+/// part1(&input);
+/// ```
+///
+/// # Panics
+///
+/// Panics if the input exists but cannot be read, so a broken input fails
+/// its tests instead of skipping them
+pub fn personal(manifest_dir: &str) -> Option<String> {
+    static NOTE: Once = Once::new();
+
+    let path = Path::new(manifest_dir).join(INPUT_FILE);
+
+    match fs::read_to_string(&path) {
+        Ok(input) => Some(input),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            NOTE.call_once(|| {
+                let _ = writeln!(
+                    io::stderr(),
+                    "note: skipping tests that need the personal input, there is none at `{}`",
+                    path.display()
+                );
+            });
+            None
+        }
+        Err(err) => panic!("{}", InputError::File(path, err)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_treat_dash_as_stdin() {
+        assert_eq!(Source::from(Path::new("-")), Source::Stdin);
+        assert_eq!(
+            Source::from(Path::new("input")),
+            Source::File(PathBuf::from("input"))
+        );
+    }
+
+    #[test]
+    fn should_prefer_command_line_path() {
+        let source = Source::resolve(Some(Path::new("other")), Path::new("input"));
+
+        assert_eq!(source, Source::File(PathBuf::from("other")));
+        assert_eq!(Source::explicit(Some(Path::new("-"))), Some(Source::Stdin));
+    }
+
+    #[test]
+    fn should_explain_missing_input() {
+        let err = load(Some(Path::new("does/not/exist")), Path::new("input")).unwrap_err();
+
+        assert!(err
+            .to_string()
+            .contains("no puzzle input at `does/not/exist`"));
+        assert_eq!(personal("does/not/exist"), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "InputError: failed to read")]
+    fn should_fail_on_unreadable_personal_input() {
+        let dir = env::temp_dir().join(format!("personal-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join(INPUT_FILE), [0xff, 0xfe]).unwrap();
+
+        personal(dir.to_str().unwrap());
+    }
+}
@@ -1,5 +1,10 @@
+pub mod answers;
+pub mod diagnostic;
 pub mod grid;
+pub mod input;
+pub mod parse;
 pub mod point;
+pub mod solution;
 
 #[cfg(test)]
 mod tests {
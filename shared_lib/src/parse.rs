@@ -0,0 +1,217 @@
+//! # Parse
+//!
+//! Module of helpers for the shapes puzzle inputs keep coming in: blocks
+//! separated by blank lines, `key: value` lines, lists, ranges like `2-4`,
+//! points like `498,4` and every integer on a line.
+//!
+//! Helpers take the whole `source` next to the slice of it they parse, so
+//! their [`Diagnostic`]s point at the right place in the input. They can be
+//! nested, as long as every slice comes from `source`:
+//!
+//! ```
+//! use shared_lib::parse;
+//!
+//! let input = "498,4 -> 498,6\n503,4 -> 502,4";
+//!
+//! let paths = input
+//!     .lines()
+//!     .map(|line| parse::list(line, " -> ", |point| parse::point::<u32>(input, point)))
+//!     .collect::<Result<Vec<_>, _>>()
+//!     .unwrap();
+//!
+//! assert_eq!(paths[1], [(503, 4), (502, 4)]);
+//! ```
+
+use std::{fmt::Display, ops::RangeInclusive, str::FromStr};
+
+use crate::diagnostic::{Diagnostic, ParseResult};
+
+/// Blocks of lines separated by one or more blank lines
+///
+/// Each block is a slice of `input` without its trailing line break.
+pub fn blocks(input: &str) -> impl Iterator<Item = &str> {
+    let mut blocks = vec![];
+    let mut start = None;
+    let mut end = 0;
+    let mut offset = 0;
+
+    for line in input.split_inclusive('\n') {
+        if line.trim().is_empty() {
+            if let Some(start) = start.take() {
+                blocks.push(&input[start..end]);
+            }
+        } else {
+            start.get_or_insert(offset);
+            end = offset + line.trim_end().len();
+        }
+
+        offset += line.len();
+    }
+
+    if let Some(start) = start {
+        blocks.push(&input[start..end]);
+    }
+
+    blocks.into_iter()
+}
+
+/// Parse `value`, which is a slice of `source`
+pub fn number<T>(source: &str, value: &str) -> ParseResult<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    value
+        .parse()
+        .map_err(|err| Diagnostic::at(source, value, format!("invalid number `{value}`: {err}")))
+}
+
+/// Split `s` in two at the first `separator`
+pub fn pair<'a>(source: &str, s: &'a str, separator: &str) -> ParseResult<(&'a str, &'a str)> {
+    s.split_once(separator).ok_or_else(|| {
+        Diagnostic::at(
+            source,
+            s,
+            format!("expected `{}` in `{s}`", separator.escape_debug()),
+        )
+    })
+}
+
+/// Split a `key: value` line, with the key trimmed
+pub fn key_value<'a>(source: &str, line: &'a str) -> ParseResult<(&'a str, &'a str)> {
+    let (key, value) = pair(source, line, ": ")?;
+
+    Ok((key.trim(), value))
+}
+
+/// Parse every item of `s` separated by `separator` with `parse`
+pub fn list<'a, T>(
+    s: &'a str,
+    separator: &str,
+    parse: impl FnMut(&'a str) -> ParseResult<T>,
+) -> ParseResult<Vec<T>> {
+    s.split(separator).map(parse).collect()
+}
+
+/// Every integer in `s`, including a leading `-`, ignoring any other text
+pub fn integers<T>(source: &str, s: &str) -> ParseResult<Vec<T>>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let bytes = s.as_bytes();
+    let mut numbers = vec![];
+    let mut index = 0;
+
+    while index < bytes.len() {
+        let start = index;
+
+        if bytes[index] == b'-' && bytes.get(index + 1).is_some_and(u8::is_ascii_digit) {
+            index += 1;
+        }
+
+        if !bytes[index].is_ascii_digit() {
+            index = start + 1;
+            continue;
+        }
+
+        while bytes.get(index).is_some_and(u8::is_ascii_digit) {
+            index += 1;
+        }
+
+        numbers.push(number(source, &s[start..index])?);
+    }
+
+    Ok(numbers)
+}
+
+/// Parse an inclusive range like `2-4`
+pub fn range<T>(source: &str, s: &str) -> ParseResult<RangeInclusive<T>>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    // Skip the first character, so a negative start is not taken as the
+    // separator
+    let separator = s
+        .char_indices()
+        .skip(1)
+        .find(|&(_, char)| char == '-')
+        .map(|(index, _)| index)
+        .ok_or_else(|| {
+            Diagnostic::at(
+                source,
+                s,
+                format!("expected a range like `2-4`, found `{s}`"),
+            )
+        })?;
+
+    let (start, end) = (&s[..separator], &s[separator + 1..]);
+
+    Ok(number(source, start)?..=number(source, end)?)
+}
+
+/// Parse a point like `498,4`
+pub fn point<T>(source: &str, s: &str) -> ParseResult<(T, T)>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let (x, y) = pair(source, s, ",")?;
+
+    Ok((number(source, x.trim())?, number(source, y.trim())?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_split_blocks_on_blank_lines() {
+        let input = "1000\n2000 \n\n\n4000\n  \n5000\n6000\n";
+
+        let blocks: Vec<&str> = blocks(input).collect();
+
+        assert_eq!(blocks, ["1000\n2000", "4000", "5000\n6000"]);
+    }
+
+    #[test]
+    fn should_find_signed_integers_in_text() -> ParseResult<()> {
+        let line = "Sensor at x=2, y=-18: closest beacon is at x=-2, y=15";
+
+        assert_eq!(integers::<i32>(line, line)?, [2, -18, -2, 15]);
+        assert_eq!(integers::<u8>("a - b -", "a - b -")?, []);
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_parse_ranges_and_points() -> ParseResult<()> {
+        let (a, b) = pair("2-4,-6--3", "2-4,-6--3", ",")?;
+
+        assert_eq!(range::<i32>("2-4,-6--3", a)?, 2..=4);
+        assert_eq!(range::<i32>("2-4,-6--3", b)?, -6..=-3);
+        assert_eq!(point::<usize>("498,4", "498,4")?, (498, 4));
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_point_at_invalid_items() {
+        let input = "Monkey 0:\n  Starting items: 79, 9x";
+        let line = input.lines().nth(1).unwrap();
+
+        let err = key_value(input, line)
+            .and_then(|(_, items)| list(items, ", ", |item| number::<u32>(input, item)))
+            .unwrap_err();
+
+        assert_eq!(err.position(), Some((2, 23)));
+
+        let err = pair(input, input, "\n\n").unwrap_err();
+
+        assert_eq!(
+            err.message(),
+            "expected `\\n\\n` in `Monkey 0:\n  Starting items: 79, 9x`"
+        );
+    }
+}
@@ -0,0 +1,31 @@
+//! # Solution
+//!
+//! Module for the common interface implemented by every calendar crate, so
+//! puzzles can be run the same way regardless of how each day is solved
+
+use std::{error::Error, fmt::Display};
+
+/// Answer to one part of a puzzle
+pub type Answer = Box<dyn Display>;
+
+/// Error returned when a part could not be solved, usually because the
+/// input failed to parse
+pub type SolutionError = Box<dyn Error + Send + Sync>;
+
+pub type SolutionResult = Result<Answer, SolutionError>;
+
+/// Solution for both parts of a puzzle
+pub trait Solution: Sync {
+    /// Parse the input without solving either part, so that parsing can be
+    /// timed on its own, or `None` for days without a separate parsing step
+    ///
+    /// Both parts still parse the input themselves, so their timings include
+    /// parsing.
+    fn parse(&self, _input: &str) -> Option<Result<(), SolutionError>> {
+        None
+    }
+
+    fn part1(&self, input: &str) -> SolutionResult;
+
+    fn part2(&self, input: &str) -> SolutionResult;
+}
@@ -0,0 +1,313 @@
+//! # Bench
+//!
+//! Module for timing the parse, part 1 and part 2 phases of each day. Every
+//! phase is repeated up to a number of times, sharing a total time budget,
+//! and reported as min/median/max. Reports can be saved as JSON and compared
+//! with a saved baseline.
+//!
+//! Both parts parse the input themselves, so their timings include parsing.
+//! Days without a separate parsing step have no parse phase.
+
+use std::{
+    fmt::{self, Display, Formatter},
+    fs,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use shared_lib::{
+    answers::{Answers, Verdict},
+    solution::SolutionError,
+};
+
+use crate::{cli::Part, day_input, part_number, registry, registry::Day, run_part};
+
+/// Parts that run for far longer than any budget or run out of memory, which
+/// are only benchmarked when their day is asked for
+const UNBOUNDED: [(Day, Phase); 1] = [(15, Phase::Part2)];
+
+/// Phase of a solution that is timed on its own
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Phase {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Display for Phase {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Phase::Parse => f.pad("parse"),
+            Phase::Part1 => f.pad("part 1"),
+            Phase::Part2 => f.pad("part 2"),
+        }
+    }
+}
+
+/// Timings of one phase, in nanoseconds
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Timing {
+    pub day: Day,
+    pub phase: Phase,
+    pub runs: usize,
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub max_ns: u64,
+}
+
+/// Timings of every phase that was benchmarked
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Report {
+    pub timings: Vec<Timing>,
+}
+
+/// Limits on how long each phase is run for
+#[derive(Debug, Clone, Copy)]
+pub struct Limits {
+    /// Maximum number of runs of each phase
+    pub repeats: usize,
+    /// Total time for the whole benchmark, split between the phases
+    ///
+    /// Every phase runs at least once, so a slow phase can exceed its share.
+    pub budget: Duration,
+    /// Also run the phases in [`UNBOUNDED`]
+    pub unbounded: bool,
+}
+
+impl Report {
+    /// Read a report previously saved with [`Report::save`]
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("failed to read report `{}`", path.display()))?;
+
+        serde_json::from_str(&text)
+            .with_context(|| format!("failed to parse report `{}`", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        fs::write(path, serde_json::to_string_pretty(self)? + "\n")
+            .with_context(|| format!("failed to write report `{}`", path.display()))
+    }
+
+    pub fn find(&self, day: Day, phase: Phase) -> Option<&Timing> {
+        self.timings
+            .iter()
+            .find(|timing| timing.day == day && timing.phase == phase)
+    }
+}
+
+impl Timing {
+    /// Summarize the duration of each run
+    pub fn new(day: Day, phase: Phase, mut samples: Vec<Duration>) -> Self {
+        samples.sort();
+
+        let nanos = |duration: Option<&Duration>| {
+            duration
+                .map(|duration| duration.as_nanos() as u64)
+                .unwrap_or_default()
+        };
+
+        Self {
+            day,
+            phase,
+            runs: samples.len(),
+            min_ns: nanos(samples.first()),
+            median_ns: nanos(samples.get(samples.len() / 2)),
+            max_ns: nanos(samples.last()),
+        }
+    }
+
+    /// Change of the median compared to `baseline`, as a percentage
+    pub fn change(&self, baseline: &Timing) -> Option<f64> {
+        if baseline.median_ns == 0 {
+            return None;
+        }
+
+        Some((self.median_ns as f64 / baseline.median_ns as f64 - 1.0) * 100.0)
+    }
+}
+
+/// Benchmark every phase of `days`, printing each timing as it is measured
+///
+/// Every part is timed, whether or not the answer store knows its answer.
+/// Recorded answers are only used to flag parts that return a wrong answer.
+pub fn bench(
+    calendar: &Path,
+    answers: &Answers,
+    days: &[Day],
+    limits: Limits,
+    baseline: Option<&Report>,
+) -> Report {
+    let start = Instant::now();
+    let mut report = Report::default();
+    let mut remaining_phases = days.len() * 3;
+
+    println!("part 1 and part 2 timings include parsing the input");
+    println!(
+        "{:>3}  {:<6}  {:>4}  {:>10}  {:>10}  {:>10}  {:>8}",
+        "day", "phase", "runs", "min", "median", "max", "change"
+    );
+
+    for &day in days {
+        let solution = registry::find(day);
+        let input = day_input(calendar, day);
+
+        for phase in [Phase::Parse, Phase::Part1, Phase::Part2] {
+            let budget = limits.budget.saturating_sub(start.elapsed()) / remaining_phases as u32;
+            remaining_phases -= 1;
+
+            let (solution, input) = match (solution, &input) {
+                (Some(solution), Ok(input)) => (solution, input),
+                (None, _) => {
+                    println!("{day:>3}  {phase:<6}  no solution is registered");
+                    continue;
+                }
+                (_, Err(err)) => {
+                    println!("{day:>3}  {phase:<6}  {err:#}");
+                    continue;
+                }
+            };
+
+            if !limits.unbounded && UNBOUNDED.contains(&(day, phase)) {
+                println!("{day:>3}  {phase:<6}  skipped, it does not finish within a budget; pass the day to run it");
+                continue;
+            }
+
+            let part = match phase {
+                // Days without a parsing step return `None` right away
+                Phase::Parse if solution.parse(input).is_none() => continue,
+                Phase::Parse => None,
+                Phase::Part1 => Some(Part::One),
+                Phase::Part2 => Some(Part::Two),
+            };
+
+            let run = || match part {
+                None => solution.parse(input).unwrap_or(Ok(())).map(|()| None),
+                Some(part) => run_part(solution, part, input).map(Some),
+            };
+
+            match measure(run, limits.repeats, budget) {
+                Ok((samples, answer)) => {
+                    let timing = Timing::new(day, phase, samples);
+                    let change = baseline
+                        .and_then(|baseline| baseline.find(day, phase))
+                        .and_then(|baseline| timing.change(baseline));
+
+                    print_timing(&timing, change);
+                    report.timings.push(timing);
+
+                    if let (Some(part), Some(answer)) = (part, answer) {
+                        let answer = answer.to_string();
+
+                        if let Verdict::Fail { expected } =
+                            answers.check(day, part_number(part), &answer)
+                        {
+                            println!(
+                                "{day:>3}  {phase:<6}  wrong answer `{answer}`, expected `{expected}`"
+                            );
+                        }
+                    }
+                }
+                Err(err) => println!("{day:>3}  {phase:<6}  failed: {err}"),
+            }
+        }
+    }
+
+    report
+}
+
+/// Time `run` until it has run `repeats` times or `budget` has passed,
+/// running it at least once
+///
+/// Returns the duration of each run and the output of the last one.
+fn measure<T>(
+    mut run: impl FnMut() -> Result<T, SolutionError>,
+    repeats: usize,
+    budget: Duration,
+) -> Result<(Vec<Duration>, T), SolutionError> {
+    let start = Instant::now();
+    let mut samples = vec![];
+
+    loop {
+        let run_start = Instant::now();
+        let output = run()?;
+        samples.push(run_start.elapsed());
+
+        if samples.len() >= repeats || start.elapsed() >= budget {
+            return Ok((samples, output));
+        }
+    }
+}
+
+fn print_timing(timing: &Timing, change: Option<f64>) {
+    let format = |nanos: u64| format!("{:.2?}", Duration::from_nanos(nanos));
+    let change = change
+        .map(|change| format!("{change:+.1}%"))
+        .unwrap_or_default();
+
+    println!(
+        "{:>3}  {:<6}  {:>4}  {:>10}  {:>10}  {:>10}  {:>8}",
+        timing.day,
+        timing.phase,
+        timing.runs,
+        format(timing.min_ns),
+        format(timing.median_ns),
+        format(timing.max_ns),
+        change
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_summarize_samples() {
+        let samples = [30, 10, 20, 50, 40].map(Duration::from_nanos).to_vec();
+
+        let timing = Timing::new(1, Phase::Part1, samples);
+
+        assert_eq!(
+            (timing.runs, timing.min_ns, timing.median_ns, timing.max_ns),
+            (5, 10, 30, 50)
+        );
+    }
+
+    #[test]
+    fn should_stop_measuring_at_repeats_or_budget() {
+        let run = || Ok(());
+
+        assert_eq!(measure(run, 5, Duration::from_secs(60)).unwrap().0.len(), 5);
+        assert_eq!(measure(run, 5, Duration::ZERO).unwrap().0.len(), 1);
+        assert!(measure(|| Err::<(), _>("bad input".into()), 5, Duration::ZERO).is_err());
+    }
+
+    #[test]
+    fn should_compare_median_with_baseline() {
+        let baseline = Timing::new(1, Phase::Parse, vec![Duration::from_nanos(200)]);
+        let timing = Timing::new(1, Phase::Parse, vec![Duration::from_nanos(150)]);
+
+        assert_eq!(timing.change(&baseline), Some(-25.0));
+    }
+
+    #[test]
+    fn should_round_trip_report_as_json() -> anyhow::Result<()> {
+        let report = Report {
+            timings: vec![Timing::new(
+                12,
+                Phase::Part2,
+                vec![Duration::from_millis(3)],
+            )],
+        };
+
+        let json = serde_json::to_string(&report)?;
+
+        assert!(json.contains("\"phase\":\"part2\""));
+        assert_eq!(serde_json::from_str::<Report>(&json)?, report);
+
+        Ok(())
+    }
+}
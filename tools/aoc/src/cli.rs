@@ -0,0 +1,123 @@
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use std::path::PathBuf;
+
+use crate::{record::Format, registry::Day};
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+pub struct Cli {
+    /// Directory containing the day crates
+    #[arg(short, long, global = true, default_value = "calendar")]
+    pub calendar: PathBuf,
+
+    /// Store of known answers checked by `verify` and `bench`
+    #[arg(short, long, global = true, default_value = "answers.toml")]
+    pub answers: PathBuf,
+
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Run the solution for a day
+    Run(RunArgs),
+    /// Check every day's answers against the answer store
+    Verify(VerifyArgs),
+    /// Time the parse, part 1 and part 2 phases of every day
+    Bench(BenchArgs),
+    /// Rebuild and re-run a day whenever its sources or inputs change
+    Watch(WatchArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct RunArgs {
+    /// Day of the puzzle to run
+    pub day: Day,
+
+    /// Only run this part of the puzzle
+    #[arg(short, long, value_enum)]
+    pub part: Option<Part>,
+
+    /// Path of the puzzle input, or `-` to read it from stdin
+    ///
+    /// [default: the path in `AOC_INPUT`, or the `input` file in the day's
+    /// `calendar/NN_*` crate]
+    #[arg(short, long)]
+    pub input: Option<PathBuf>,
+
+    /// Format to print the answers in
+    #[arg(short, long, value_enum, default_value_t)]
+    pub format: Format,
+}
+
+#[derive(Args, Debug)]
+pub struct VerifyArgs {
+    /// Only verify these days
+    ///
+    /// [default: every registered day]
+    pub days: Vec<Day>,
+
+    /// Format to print the results in
+    #[arg(short, long, value_enum, default_value_t)]
+    pub format: Format,
+}
+
+#[derive(Args, Debug)]
+pub struct BenchArgs {
+    /// Only benchmark these days
+    ///
+    /// [default: every registered day, skipping parts that never finish within
+    /// a budget]
+    pub days: Vec<Day>,
+
+    /// Maximum number of times each phase is run
+    #[arg(short = 'n', long, default_value_t = 10)]
+    pub repeats: usize,
+
+    /// Total number of seconds to spend, shared between every phase
+    #[arg(long, default_value_t = 60)]
+    pub budget: u64,
+
+    /// Save the timings as JSON to this file
+    #[arg(long)]
+    pub json: Option<PathBuf>,
+
+    /// Compare median timings with a report saved by `--json`
+    #[arg(long)]
+    pub baseline: Option<PathBuf>,
+}
+
+#[derive(Args, Debug)]
+pub struct WatchArgs {
+    /// Day of the puzzle to watch
+    pub day: Day,
+
+    /// Build the solutions with optimizations
+    #[arg(short, long)]
+    pub release: bool,
+}
+
+/// Puzzle part to run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Part {
+    #[value(name = "1")]
+    One,
+    #[value(name = "2")]
+    Two,
+}
+
+pub fn parse_args() -> Cli {
+    Cli::parse()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn should_have_valid_arguments() {
+        Cli::command().debug_assert();
+    }
+}
@@ -0,0 +1,199 @@
+mod bench;
+mod cli;
+mod record;
+mod registry;
+mod verify;
+mod watch;
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use anyhow::Context;
+use shared_lib::{
+    answers::Answers,
+    input::{Source, INPUT_FILE},
+    solution::{Solution, SolutionResult},
+};
+
+use crate::{
+    bench::{Limits, Report},
+    cli::{BenchArgs, Cli, Command, Part, RunArgs, VerifyArgs, WatchArgs},
+    record::Record,
+    registry::Day,
+};
+
+/// Main entry point to fully execute command-line program
+pub fn execute() -> anyhow::Result<()> {
+    let Cli {
+        calendar,
+        answers,
+        command,
+    } = cli::parse_args();
+
+    match command {
+        Command::Run(RunArgs {
+            day,
+            part,
+            input,
+            format,
+        }) => {
+            let solution = registry::find(day)
+                .with_context(|| format!("no solution is registered for day {day}"))?;
+
+            // Only look for the day crate when no input was given, so `AOC_INPUT`
+            // also works outside of the workspace
+            let source = match Source::explicit(input.as_deref()) {
+                Some(source) => source,
+                None => Source::File(find_day_dir(&calendar, day)?.join(INPUT_FILE)),
+            };
+            let input = source.read()?;
+
+            let parts = match part {
+                Some(part) => vec![part],
+                None => vec![Part::One, Part::Two],
+            };
+
+            let records: Vec<Record> = parts
+                .into_iter()
+                .map(|part| Record::run(solution, day, part, &input))
+                .collect();
+
+            record::print(&records, format, |record| {
+                match (&record.answer, &record.error) {
+                    (Some(answer), _) => format!("Part {}: {answer}", record.part),
+                    (None, error) => format!(
+                        "Part {} failed: {}",
+                        record.part,
+                        error.as_deref().unwrap_or_default()
+                    ),
+                }
+            })?;
+
+            ensure_no_failures(&records, "parts failed")
+        }
+        Command::Verify(VerifyArgs { days, format }) => {
+            let answers = Answers::load(&answers)?;
+            let days = registry::or_all(days);
+
+            let records = verify::verify(&calendar, &answers, &days);
+            record::print(&records, format, verify::to_text)?;
+
+            ensure_no_failures(&records, "answers did not match the answer store")
+        }
+        Command::Bench(BenchArgs {
+            days,
+            repeats,
+            budget,
+            json,
+            baseline,
+        }) => {
+            let answers = Answers::load(&answers)?;
+            let baseline = baseline.as_deref().map(Report::load).transpose()?;
+            let limits = Limits {
+                repeats,
+                budget: Duration::from_secs(budget),
+                unbounded: !days.is_empty(),
+            };
+
+            let report = bench::bench(
+                &calendar,
+                &answers,
+                &registry::or_all(days),
+                limits,
+                baseline.as_ref(),
+            );
+
+            if let Some(path) = json {
+                report.save(&path)?;
+            }
+
+            Ok(())
+        }
+        Command::Watch(WatchArgs { day, release }) => watch::watch(&calendar, day, release),
+    }
+}
+
+/// Fail with `message` when any of `records` failed, after they were printed
+fn ensure_no_failures(records: &[Record], message: &str) -> anyhow::Result<()> {
+    let failures = records.iter().filter(|record| record.is_failure()).count();
+
+    if failures > 0 {
+        anyhow::bail!("{failures} {message}");
+    }
+
+    Ok(())
+}
+
+/// Run one part of a solution
+pub fn run_part(solution: &dyn Solution, part: Part, input: &str) -> SolutionResult {
+    match part {
+        Part::One => solution.part1(input),
+        Part::Two => solution.part2(input),
+    }
+}
+
+fn part_number(part: Part) -> u8 {
+    match part {
+        Part::One => 1,
+        Part::Two => 2,
+    }
+}
+
+/// Read the `input` file of the day's crate in `calendar`
+fn day_input(calendar: &Path, day: Day) -> anyhow::Result<String> {
+    let path = find_day_dir(calendar, day)?.join(INPUT_FILE);
+
+    Ok(Source::File(path).read()?)
+}
+
+/// Find the day crate in `calendar` whose directory name starts with the day, e.g. `05_`
+fn find_day_dir(calendar: &Path, day: Day) -> anyhow::Result<PathBuf> {
+    let prefix = format!("{day:02}_");
+
+    let entries = fs::read_dir(calendar)
+        .with_context(|| format!("failed to read calendar directory `{}`", calendar.display()))?;
+
+    for entry in entries {
+        let entry = entry?;
+
+        if entry.file_name().to_string_lossy().starts_with(&prefix) && entry.file_type()?.is_dir() {
+            return Ok(entry.path());
+        }
+    }
+
+    anyhow::bail!("no day crate for day {day} in `{}`", calendar.display())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_run_each_part_of_a_registered_solution() -> anyhow::Result<()> {
+        let solution = registry::find(1).context("day 1 should be registered")?;
+        let input = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n";
+
+        let part1 = run_part(solution, Part::One, input).map_err(|err| anyhow::anyhow!(err))?;
+        let part2 = run_part(solution, Part::Two, input).map_err(|err| anyhow::anyhow!(err))?;
+
+        assert_eq!(part1.to_string(), "24000");
+        assert_eq!(part2.to_string(), "45000");
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_find_day_dir_in_calendar() -> anyhow::Result<()> {
+        let calendar = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../calendar");
+
+        let dir = find_day_dir(&calendar, 12)?;
+
+        assert!(dir.ends_with("12_hill_climbing_algorithm"));
+        assert!(find_day_dir(&calendar, 25).is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,5 @@
+fn main() -> anyhow::Result<()> {
+    aoc::execute()?;
+
+    Ok(())
+}
@@ -0,0 +1,174 @@
+//! # Record
+//!
+//! Module for the result of running one part of a day, which is printed as
+//! text or as JSON for other tools to consume
+
+use std::{
+    fmt::{self, Display, Formatter},
+    time::Instant,
+};
+
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use shared_lib::solution::Solution;
+
+use crate::{cli::Part, part_number, registry::Day, run_part};
+
+/// Format results are printed in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    #[default]
+    Text,
+    /// Array of records with `day`, `part`, `answer`, `duration_ns` and `status`
+    Json,
+}
+
+/// Status of one part
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    /// Part was solved, without checking the answer
+    Ok,
+    /// Part could not be solved, because of a missing input or an error
+    Error,
+    /// Answer matched the answer store
+    Pass,
+    /// Answer did not match the answer store
+    Fail,
+    /// Answer store has no answer for the part, so it was not run
+    Missing,
+}
+
+/// Result of running one part of a day
+///
+/// Answers are always strings, since some are text or span several lines
+/// like day 10's part 2.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Record {
+    pub day: Day,
+    pub part: u8,
+    pub answer: Option<String>,
+    pub duration_ns: Option<u64>,
+    pub status: Status,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl Record {
+    /// Run one part of `solution`, timing how long it takes
+    pub fn run(solution: &dyn Solution, day: Day, part: Part, input: &str) -> Self {
+        let start = Instant::now();
+        let result = run_part(solution, part, input);
+        let duration_ns = Some(start.elapsed().as_nanos() as u64);
+
+        match result {
+            Ok(answer) => Self {
+                answer: Some(answer.to_string()),
+                duration_ns,
+                ..Self::new(day, part, Status::Ok)
+            },
+            Err(err) => Self {
+                duration_ns,
+                error: Some(err.to_string()),
+                ..Self::new(day, part, Status::Error)
+            },
+        }
+    }
+
+    /// Record for a part that was not run
+    pub fn new(day: Day, part: Part, status: Status) -> Self {
+        Self {
+            day,
+            part: part_number(part),
+            answer: None,
+            duration_ns: None,
+            status,
+            expected: None,
+            error: None,
+        }
+    }
+
+    pub fn error(day: Day, part: Part, error: String) -> Self {
+        Self {
+            error: Some(error),
+            ..Self::new(day, part, Status::Error)
+        }
+    }
+
+    pub fn is_failure(&self) -> bool {
+        matches!(self.status, Status::Error | Status::Fail)
+    }
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Status::Ok => f.pad("ok"),
+            Status::Error => f.pad("ERROR"),
+            Status::Pass => f.pad("pass"),
+            Status::Fail => f.pad("FAIL"),
+            Status::Missing => f.pad("missing"),
+        }
+    }
+}
+
+/// Print `records` in `format`, with `text` rendering a single record
+pub fn print(
+    records: &[Record],
+    format: Format,
+    text: impl Fn(&Record) -> String,
+) -> anyhow::Result<()> {
+    match format {
+        Format::Text => {
+            for record in records {
+                println!("{}", text(record));
+            }
+        }
+        Format::Json => println!("{}", serde_json::to_string_pretty(records)?),
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+
+    #[test]
+    fn should_serialize_multi_line_answers_as_strings() -> anyhow::Result<()> {
+        let record = Record {
+            answer: Some("#..#\n.##.".to_string()),
+            duration_ns: Some(1200),
+            ..Record::new(10, Part::Two, Status::Ok)
+        };
+
+        let json = serde_json::to_value(&record)?;
+
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "day": 10,
+                "part": 2,
+                "answer": "#..#\n.##.",
+                "duration_ns": 1200,
+                "status": "ok",
+            })
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_record_errors() {
+        let solution = registry::find(9).unwrap();
+
+        let record = Record::run(solution, 9, Part::One, "X 1");
+
+        assert_eq!(record.status, Status::Error);
+        assert!(record.error.is_some() && record.answer.is_none());
+        assert!(record.is_failure());
+    }
+}
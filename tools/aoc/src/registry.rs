@@ -0,0 +1,64 @@
+//! # Registry
+//!
+//! Module mapping each day of the calendar to its [`Solution`]
+
+use shared_lib::solution::Solution;
+
+pub type Day = u8;
+
+/// Solution of every day crate in the calendar, in order
+///
+/// New day crates need to be added here and to the runner's dependencies.
+pub const SOLUTIONS: [(Day, &dyn Solution); 15] = [
+    (1, &calorie_counting::Puzzle),
+    (2, &rock_paper_scissors::Puzzle),
+    (3, &rucksack_reorganization::Puzzle),
+    (4, &camp_cleanup::Puzzle),
+    (5, &supply_stack::Puzzle),
+    (6, &tuning_trouble::Puzzle),
+    (7, &no_space_left_on_device::Puzzle),
+    (8, &treetop_tree_house::Puzzle),
+    (9, &rope_bridge::Puzzle),
+    (10, &cathode_ray_tube::Puzzle),
+    (11, &monkey_in_the_middle::Puzzle),
+    (12, &hill_climbing_algorithm::Puzzle),
+    (13, &distress_signal::Puzzle),
+    (14, &regolith_reservoir::Puzzle),
+    (15, &beacon_exclusion_zone::Puzzle),
+];
+
+/// Find the solution for `day`
+pub fn find(day: Day) -> Option<&'static dyn Solution> {
+    SOLUTIONS
+        .iter()
+        .find(|(solution_day, _)| *solution_day == day)
+        .map(|(_, solution)| *solution)
+}
+
+/// `days`, or every registered day when it is empty
+pub fn or_all(days: Vec<Day>) -> Vec<Day> {
+    if days.is_empty() {
+        SOLUTIONS.iter().map(|(day, _)| *day).collect()
+    } else {
+        days
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_find_solution_by_day() {
+        assert!(find(1).is_some());
+        assert!(find(15).is_some());
+        assert!(find(25).is_none());
+    }
+
+    #[test]
+    fn should_register_each_day_once_in_order() {
+        let days: Vec<Day> = SOLUTIONS.iter().map(|(day, _)| *day).collect();
+
+        assert_eq!(days, (1..=15).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,116 @@
+//! # Verify
+//!
+//! Module for checking solutions against the answers recorded in the answer
+//! store. Parts without a recorded answer are reported as missing without
+//! being run, so unfinished or slow solutions do not hold up the check.
+
+use std::path::Path;
+
+use shared_lib::answers::{Answers, Verdict};
+
+use crate::{
+    cli::Part,
+    day_input, part_number,
+    record::{Record, Status},
+    registry,
+    registry::Day,
+};
+
+/// Verify both parts of each of `days`
+pub fn verify(calendar: &Path, answers: &Answers, days: &[Day]) -> Vec<Record> {
+    days.iter()
+        .flat_map(|&day| verify_day(calendar, answers, day))
+        .collect()
+}
+
+/// Render a record as one line of text
+pub fn to_text(record: &Record) -> String {
+    let Record { day, part, .. } = record;
+    let detail = match (&record.expected, &record.error) {
+        (_, Some(err)) => format!("  {err}"),
+        (Some(expected), None) if record.status == Status::Fail => {
+            format!("  expected `{expected}`")
+        }
+        _ => String::new(),
+    };
+
+    format!("day {day:>2} part {part}  {:<7}{detail}", record.status)
+        .trim_end()
+        .to_string()
+}
+
+/// Verify both parts of one day
+fn verify_day(calendar: &Path, answers: &Answers, day: Day) -> [Record; 2] {
+    let missing = |part| answers.get(day, part_number(part)).is_none();
+
+    if missing(Part::One) && missing(Part::Two) {
+        return [Part::One, Part::Two].map(|part| Record::new(day, part, Status::Missing));
+    }
+
+    let input = match day_input(calendar, day) {
+        Ok(input) => input,
+        Err(err) => {
+            return [Part::One, Part::Two].map(|part| Record::error(day, part, format!("{err:#}")))
+        }
+    };
+
+    [Part::One, Part::Two].map(|part| check_part(answers, day, part, &input))
+}
+
+/// Run one part and compare it with the recorded answer
+pub fn check_part(answers: &Answers, day: Day, part: Part, input: &str) -> Record {
+    if answers.get(day, part_number(part)).is_none() {
+        return Record::new(day, part, Status::Missing);
+    }
+
+    let Some(solution) = registry::find(day) else {
+        return Record::error(
+            day,
+            part,
+            format!("no solution is registered for day {day}"),
+        );
+    };
+
+    let mut record = Record::run(solution, day, part, input);
+
+    if let Some(answer) = &record.answer {
+        match answers.check(day, part_number(part), answer) {
+            Verdict::Pass => record.status = Status::Pass,
+            Verdict::Fail { expected } => {
+                record.status = Status::Fail;
+                record.expected = Some(expected);
+            }
+            Verdict::Missing => record.status = Status::Missing,
+        }
+    }
+
+    record
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASIC_INPUT: &str =
+        "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n";
+
+    #[test]
+    fn should_check_parts_against_answers() {
+        let answers: Answers = "[day.1]\npart1 = \"24000\"\n[day.2]\npart1 = \"1\""
+            .parse()
+            .unwrap();
+
+        assert_eq!(
+            check_part(&answers, 1, Part::One, BASIC_INPUT).status,
+            Status::Pass
+        );
+        assert_eq!(
+            check_part(&answers, 1, Part::Two, BASIC_INPUT),
+            Record::new(1, Part::Two, Status::Missing)
+        );
+
+        let failed = check_part(&answers, 2, Part::One, "A Y");
+        assert!(failed.is_failure());
+        assert_eq!(to_text(&failed), "day  2 part 1  FAIL     expected `1`");
+    }
+}
@@ -0,0 +1,262 @@
+//! # Watch
+//!
+//! Module for re-running a day whenever its sources or inputs change. The
+//! solutions are compiled into the runner, so every run goes through
+//! `cargo run` to rebuild it first.
+
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::{find_day_dir, record::Record, registry::Day};
+
+/// Time between two checks for changed files
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Inputs each run is solved for, when they exist in the day crate
+const INPUTS: [&str; 2] = ["basic_input", "input"];
+
+/// Answers of both parts, or the error that stopped a run
+type Outcome = Result<[Option<String>; 2], String>;
+
+/// Modification time of every file under `paths`
+type Snapshot = BTreeMap<PathBuf, SystemTime>;
+
+/// Watch the day crate's `src/`, `input` and `basic_input`, re-running both
+/// parts after every change until interrupted
+pub fn watch(calendar: &Path, day: Day, release: bool) -> anyhow::Result<()> {
+    let day_dir = find_day_dir(calendar, day)?;
+    let watched: Vec<PathBuf> = ["src", "input", "basic_input"]
+        .iter()
+        .map(|name| day_dir.join(name))
+        .collect();
+
+    let mut previous: BTreeMap<&str, Outcome> = BTreeMap::new();
+    let mut last_snapshot = None;
+
+    loop {
+        let current_snapshot = snapshot(&watched);
+
+        if last_snapshot.as_ref() != Some(&current_snapshot) {
+            println!("running day {day} in `{}`", day_dir.display());
+
+            for name in INPUTS {
+                let input = day_dir.join(name);
+                if !input.exists() {
+                    continue;
+                }
+
+                let outcome = run_day(calendar, day, &input, release);
+                print!("{}", side_by_side(name, previous.get(name), &outcome));
+
+                // Keep comparing with the last answers that were found
+                if outcome.is_ok() {
+                    previous.insert(name, outcome);
+                }
+            }
+
+            println!("watching for changes, press Ctrl-C to stop\n");
+            last_snapshot = Some(current_snapshot);
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Collect the modification time of every file under `paths`, skipping ones
+/// that cannot be read
+fn snapshot(paths: &[PathBuf]) -> Snapshot {
+    let mut times = Snapshot::new();
+    let mut pending = paths.to_vec();
+
+    while let Some(path) = pending.pop() {
+        let Ok(metadata) = fs::metadata(&path) else {
+            continue;
+        };
+
+        if metadata.is_dir() {
+            if let Ok(entries) = fs::read_dir(&path) {
+                pending.extend(entries.flatten().map(|entry| entry.path()));
+            }
+        } else if let Ok(modified) = metadata.modified() {
+            times.insert(path, modified);
+        }
+    }
+
+    times
+}
+
+/// Rebuild the runner and run both parts of `day` with `input`
+fn run_day(calendar: &Path, day: Day, input: &Path, release: bool) -> Outcome {
+    let mut command = Command::new(option_env!("CARGO").unwrap_or("cargo"));
+    command.args(["run", "--quiet", "--package", env!("CARGO_PKG_NAME")]);
+    if release {
+        command.arg("--release");
+    }
+    command
+        .arg("--")
+        .arg("--calendar")
+        .arg(calendar)
+        .args(["run", &day.to_string(), "--format", "json", "--input"])
+        .arg(input);
+
+    let output = command
+        .output()
+        .map_err(|err| format!("failed to run cargo: {err}"))?;
+
+    // Build errors leave stdout empty, while failed parts are still printed
+    match serde_json::from_slice::<Vec<Record>>(&output.stdout) {
+        Ok(records) => answers(&records),
+        Err(_) => Err(String::from_utf8_lossy(&output.stderr).trim().to_string()),
+    }
+}
+
+/// Answers of both parts, or the errors of the parts that failed
+fn answers(records: &[Record]) -> Outcome {
+    let errors: Vec<String> = records
+        .iter()
+        .filter_map(|record| Some(format!("part {}: {}", record.part, record.error.as_ref()?)))
+        .collect();
+
+    if !errors.is_empty() {
+        return Err(errors.join("\n"));
+    }
+
+    let answer = |part| {
+        records
+            .iter()
+            .find(|record| record.part == part)
+            .and_then(|record| record.answer.clone())
+    };
+
+    Ok([answer(1), answer(2)])
+}
+
+/// Render previous and current answers for one input in two columns, or the
+/// error of a failed run
+fn side_by_side(name: &str, previous: Option<&Outcome>, current: &Outcome) -> String {
+    let mut out = String::new();
+
+    if let Err(err) = current {
+        writeln!(out, "{name}: failed\n{err}").ok();
+        return out;
+    }
+
+    let cell = |outcome: Option<&Outcome>, index: usize| match outcome {
+        Some(Ok(answers)) => answers[index].clone().unwrap_or_default(),
+        Some(Err(_)) => "(failed)".to_string(),
+        None => String::new(),
+    };
+
+    let rows: Vec<(String, String)> = (0..2)
+        .map(|index| (cell(previous, index), cell(Some(current), index)))
+        .collect();
+    let width = rows
+        .iter()
+        .flat_map(|(previous, _)| previous.lines())
+        .map(str::len)
+        .chain([8])
+        .max()
+        .unwrap_or_default();
+
+    writeln!(out, "{name:<11}  {:<width$}  current", "previous").ok();
+
+    for (index, (previous, current)) in rows.iter().enumerate() {
+        let marker = if previous != current && !previous.is_empty() {
+            "  (changed)"
+        } else {
+            ""
+        };
+
+        let previous_lines: Vec<&str> = previous.lines().collect();
+        let current_lines: Vec<&str> = current.lines().collect();
+        let height = previous_lines.len().max(current_lines.len()).max(1);
+
+        for line in 0..height {
+            let label = if line == 0 {
+                format!("part {}", index + 1)
+            } else {
+                String::new()
+            };
+            let left = previous_lines.get(line).copied().unwrap_or_default();
+            let right = current_lines.get(line).copied().unwrap_or_default();
+            let marker = if line == 0 { marker } else { "" };
+
+            writeln!(out, "{label:<11}  {left:<width$}  {right}{marker}").ok();
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{cli::Part, record::Status};
+
+    #[test]
+    fn should_collect_answers_or_errors_from_records() {
+        let solved = Record {
+            answer: Some("###..\n#..#.".to_string()),
+            ..Record::new(10, Part::Two, Status::Ok)
+        };
+        let failed = Record::error(10, Part::One, "bad instruction".to_string());
+
+        assert_eq!(
+            answers(std::slice::from_ref(&solved)),
+            Ok([None, Some("###..\n#..#.".to_string())])
+        );
+        assert_eq!(
+            answers(&[failed, solved]),
+            Err("part 1: bad instruction".to_string())
+        );
+    }
+
+    #[test]
+    fn should_show_previous_and_current_answers() {
+        let previous = Ok([Some("394".to_string()), Some("400".to_string())]);
+        let current = Ok([Some("394".to_string()), Some("388".to_string())]);
+
+        let expected = "\
+input        previous  current
+part 1       394       394
+part 2       400       388  (changed)
+";
+
+        assert_eq!(side_by_side("input", Some(&previous), &current), expected);
+    }
+
+    #[test]
+    fn should_show_failures_inline() {
+        let current = Err("error[E0308]: mismatched types".to_string());
+
+        assert_eq!(
+            side_by_side("input", None, &current),
+            "input: failed\nerror[E0308]: mismatched types\n"
+        );
+    }
+
+    #[test]
+    fn should_notice_changed_files() -> std::io::Result<()> {
+        let dir = std::env::temp_dir().join(format!("aoc-watch-{}", std::process::id()));
+        fs::create_dir_all(dir.join("src"))?;
+        fs::write(dir.join("src").join("lib.rs"), "")?;
+
+        let paths = [dir.join("src"), dir.join("input")];
+        let before = snapshot(&paths);
+        fs::write(dir.join("input"), "1")?;
+        let after = snapshot(&paths);
+        fs::remove_dir_all(&dir)?;
+
+        assert_eq!(before.len(), 1);
+        assert_ne!(before, after);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,212 @@
+//! # Batch
+//!
+//! Module for fetching a range of days concurrently into their day crates
+
+use std::{ops::RangeInclusive, path::PathBuf};
+
+use chrono::{DateTime, FixedOffset};
+use futures::{stream, StreamExt};
+
+use crate::{
+    cli::OutputArgs,
+    client::AocClient,
+    config::OutdirPattern,
+    date::{self, Day, Year},
+    fetch_puzzle,
+};
+
+/// What happened to one day of a batch
+enum Fetched {
+    Saved(PathBuf),
+    NoDayCrate,
+    Locked(DateTime<FixedOffset>),
+}
+
+/// Fetch every day in `days` that is unlocked at `now` into the day directory
+/// matching `pattern`, running at most `concurrency` fetches at a time
+///
+/// Days without a day crate or that are still locked are skipped.
+pub async fn fetch_days(
+    aoc: &AocClient,
+    year: Year,
+    days: RangeInclusive<Day>,
+    pattern: &OutdirPattern,
+    output: &OutputArgs,
+    concurrency: usize,
+    now: DateTime<FixedOffset>,
+) -> anyhow::Result<()> {
+    let results: Vec<(Day, anyhow::Result<Fetched>)> = stream::iter(days)
+        .map(|day| async move {
+            let result = async {
+                if let Err(err) = date::check_unlocked(year, day, now) {
+                    return match err.unlocks_at() {
+                        Some(unlocks_at) => Ok(Fetched::Locked(unlocks_at)),
+                        None => Err(err.into()),
+                    };
+                }
+
+                let Some(outdir) = pattern.find(year, day).await? else {
+                    return Ok(Fetched::NoDayCrate);
+                };
+
+                fetch_puzzle(aoc, year, day, &outdir, output).await?;
+
+                Ok(Fetched::Saved(outdir))
+            }
+            .await;
+
+            (day, result)
+        })
+        .buffer_unordered(concurrency.max(1))
+        .collect()
+        .await;
+
+    let mut failed = 0;
+
+    for (day, result) in results {
+        match result {
+            Ok(Fetched::Saved(outdir)) => println!("day {day}: saved to `{}`", outdir.display()),
+            Ok(Fetched::NoDayCrate) => eprintln!("day {day}: skipped, no day crate found"),
+            Ok(Fetched::Locked(unlocks_at)) => {
+                eprintln!("day {day}: skipped, locked until {unlocks_at}")
+            }
+            Err(err) => {
+                failed += 1;
+                eprintln!("day {day}: {err}");
+            }
+        }
+    }
+
+    if failed > 0 {
+        anyhow::bail!("failed to fetch {failed} day(s)");
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{cache::Cache, config::Config};
+    use std::time::Duration;
+    use wiremock::{
+        matchers::{method, path_regex},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    fn output() -> OutputArgs {
+        OutputArgs {
+            input_filename: None,
+            puzzle_filename: "puzzle.md".to_string(),
+            example_filename: "basic_input".to_string(),
+            example_index: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn should_find_day_dir_by_prefix() -> anyhow::Result<()> {
+        let calendar = tempfile::tempdir()?;
+        std::fs::create_dir(calendar.path().join("05_supply_stack"))?;
+        std::fs::create_dir(calendar.path().join("15_beacon_exclusion_zone"))?;
+        let pattern = Config::default().outdir_pattern(calendar.path());
+
+        assert_eq!(
+            pattern.find(2022, 5).await?,
+            Some(calendar.path().join("05_supply_stack"))
+        );
+        assert_eq!(pattern.find(2022, 1).await?, None);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_fetch_days_into_matching_crates() -> anyhow::Result<()> {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path_regex(r"^/2022/day/\d+/input$"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("input data\n"))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path_regex(r"^/2022/day/\d+$"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                "<article class=\"day-desc\"><p>For example:</p><pre><code>1\n</code></pre></article>",
+            ))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let calendar = tempfile::tempdir()?;
+        std::fs::create_dir(calendar.path().join("01_calorie_counting"))?;
+        std::fs::create_dir(calendar.path().join("03_rucksack_reorganization"))?;
+
+        let cache_dir = tempfile::tempdir()?;
+        let cache = Cache::new(cache_dir.path(), Duration::ZERO, false);
+        let aoc = AocClient::new("abc123", server.uri().parse()?, cache)?;
+
+        let pattern = Config::default().outdir_pattern(calendar.path());
+
+        fetch_days(&aoc, 2022, 1..=3, &pattern, &output(), 2, date::now()).await?;
+
+        for dir in ["01_calorie_counting", "03_rucksack_reorganization"] {
+            let input = std::fs::read_to_string(calendar.path().join(dir).join("input"))?;
+            assert_eq!(input, "input data\n");
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_skip_days_that_are_still_locked() -> anyhow::Result<()> {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path_regex(r"^/2022/day/1/input$"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("input data\n"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path_regex(r"^/2022/day/1$"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                "<article class=\"day-desc\"><p>For example:</p><pre><code>1\n</code></pre></article>",
+            ))
+            .mount(&server)
+            .await;
+
+        let calendar = tempfile::tempdir()?;
+        for dir in [
+            "01_calorie_counting",
+            "02_rock_paper_scissors",
+            "03_rucksack_reorganization",
+        ] {
+            std::fs::create_dir(calendar.path().join(dir))?;
+        }
+
+        let cache_dir = tempfile::tempdir()?;
+        let cache = Cache::new(cache_dir.path(), Duration::ZERO, false);
+        let aoc = AocClient::new("abc123", server.uri().parse()?, cache)?;
+
+        let pattern = Config::default().outdir_pattern(calendar.path());
+        // Only day 1 has unlocked during an ongoing event
+        let now = date::unlock_time(2022, 1) + chrono::Duration::hours(12);
+
+        fetch_days(&aoc, 2022, 1..=25, &pattern, &output(), 4, now).await?;
+
+        assert!(calendar.path().join("01_calorie_counting/input").exists());
+        assert!(!calendar
+            .path()
+            .join("02_rock_paper_scissors/input")
+            .exists());
+        assert!(!calendar
+            .path()
+            .join("03_rucksack_reorganization/input")
+            .exists());
+
+        Ok(())
+    }
+}
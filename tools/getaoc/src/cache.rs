@@ -0,0 +1,388 @@
+//! # Cache
+//!
+//! Module for an on-disk response cache that also keeps real network
+//! requests at least a minimum interval apart
+
+use std::{
+    env,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::Context;
+use reqwest::Client;
+use tokio::{fs, sync::Mutex, time};
+use url::Url;
+
+use crate::{
+    date::{Day, Year},
+    error::{read_response, Error},
+};
+
+/// File in the cache directory that records the time of the last network request
+const LAST_REQUEST_FILE: &str = ".last_request";
+
+/// Predicate for [`Cache::get`] that stores every response
+pub fn always(_: &str) -> bool {
+    true
+}
+
+/// Hash of a session, used to name its files without revealing it
+///
+/// This is FNV-1a, which unlike [`std::hash::DefaultHasher`] stays the same
+/// across Rust versions.
+pub fn session_hash(session: &str) -> String {
+    let hash = session.bytes().fold(0xcbf29ce484222325_u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    });
+
+    format!("{hash:016x}")
+}
+
+/// Cache of puzzle resources stored under
+/// `{dir}/{session}/{year}/{day}/{resource}`
+///
+/// Resources like inputs differ between accounts, so each session gets its
+/// own directory, see [`Cache::for_session`].
+#[derive(Debug)]
+pub struct Cache {
+    dir: PathBuf,
+    /// hash of the session whose resources are stored, see [`session_hash`]
+    session: Option<String>,
+    /// minimum time between two real network requests
+    min_interval: Duration,
+    /// skip reading cached responses; fresh responses are still stored
+    refresh: bool,
+    /// serializes waiting for the minimum interval between requests
+    lock: Mutex<()>,
+}
+
+impl Cache {
+    pub fn new<P: Into<PathBuf>>(dir: P, min_interval: Duration, refresh: bool) -> Self {
+        Self {
+            dir: dir.into(),
+            session: None,
+            min_interval,
+            refresh,
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// Default cache directory, `$XDG_CACHE_HOME/getaoc` or the platform equivalent
+    pub fn default_dir() -> PathBuf {
+        dirs::cache_dir()
+            .unwrap_or_else(env::temp_dir)
+            .join(env!("CARGO_PKG_NAME"))
+    }
+
+    /// Store resources in a directory of their own for `session`
+    pub fn for_session(self, session: &str) -> Self {
+        Self {
+            session: Some(session_hash(session)),
+            ..self
+        }
+    }
+
+    /// Directory the cache is stored in
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Directory the resources of the current session are stored in
+    fn session_dir(&self) -> PathBuf {
+        match &self.session {
+            Some(session) => self.dir.join(session),
+            None => self.dir.clone(),
+        }
+    }
+
+    /// Path of a cached resource
+    pub fn path(&self, year: Year, day: Day, resource: &str) -> PathBuf {
+        self.session_dir()
+            .join(year.to_string())
+            .join(format!("{day:02}"))
+            .join(resource)
+    }
+
+    /// Path of a cached resource that belongs to a whole event, like a leaderboard
+    pub fn event_path(&self, year: Year, resource: &str) -> PathBuf {
+        self.session_dir().join(year.to_string()).join(resource)
+    }
+
+    /// Get the text at `url`, serving it from the cache when possible
+    ///
+    /// Responses that fail the checks in [`read_response`] are returned as
+    /// an [`Error`] and never stored.
+    ///
+    /// The response is only stored when `store` returns true for it, which lets
+    /// callers avoid caching pages that are still expected to change.
+    pub async fn get(
+        &self,
+        client: &Client,
+        url: Url,
+        (year, day, resource): (Year, Day, &str),
+        store: fn(&str) -> bool,
+    ) -> anyhow::Result<String> {
+        let path = self.path(year, day, resource);
+
+        if !self.refresh {
+            if let Ok(cached) = fs::read_to_string(&path).await {
+                return Ok(cached);
+            }
+        }
+
+        let text = self.fetch(client, url).await?;
+
+        if store(&text) {
+            write_cache_file(&path, &text).await?;
+        }
+
+        Ok(text)
+    }
+
+    /// Get the text at `url`, serving it from the cached file at `path` if
+    /// that was stored less than `max_age` ago
+    ///
+    /// This is for resources that keep changing but should not be requested
+    /// often, like private leaderboards.
+    pub async fn get_recent(
+        &self,
+        client: &Client,
+        url: Url,
+        path: &Path,
+        max_age: Duration,
+    ) -> anyhow::Result<String> {
+        if !self.refresh {
+            let age = fs::metadata(path)
+                .await
+                .and_then(|metadata| metadata.modified())
+                .ok()
+                .and_then(|modified| modified.elapsed().ok());
+
+            if age.is_some_and(|age| age < max_age) {
+                if let Ok(cached) = fs::read_to_string(path).await {
+                    return Ok(cached);
+                }
+            }
+        }
+
+        let text = self.fetch(client, url).await?;
+        write_cache_file(path, &text).await?;
+
+        Ok(text)
+    }
+
+    /// Request `url` over the network without caching it, respecting the
+    /// minimum interval
+    pub async fn fetch(&self, client: &Client, url: Url) -> anyhow::Result<String> {
+        self.throttle().await?;
+        let response = client.get(url).send().await.map_err(Error::from)?;
+
+        Ok(read_response(response).await?)
+    }
+
+    /// Wait until the minimum interval since the last network request has passed
+    ///
+    /// Only the start of requests is spaced out, so concurrent requests can
+    /// still overlap once they are sent.
+    pub async fn throttle(&self) -> anyhow::Result<()> {
+        let _guard = self.lock.lock().await;
+        let marker = self.dir.join(LAST_REQUEST_FILE);
+
+        if let Some(last_request) = read_timestamp(&marker).await {
+            let elapsed = SystemTime::now()
+                .duration_since(last_request)
+                .unwrap_or_default();
+
+            if elapsed < self.min_interval {
+                time::sleep(self.min_interval - elapsed).await;
+            }
+        }
+
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?;
+        write_cache_file(&marker, &now.as_millis().to_string()).await?;
+
+        Ok(())
+    }
+}
+
+/// Read a timestamp stored as milliseconds since the unix epoch
+async fn read_timestamp(path: &Path) -> Option<SystemTime> {
+    let millis: u64 = fs::read_to_string(path).await.ok()?.trim().parse().ok()?;
+
+    Some(UNIX_EPOCH + Duration::from_millis(millis))
+}
+
+async fn write_cache_file(path: &Path, data: &str) -> anyhow::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).await?;
+    }
+
+    fs::write(path, data)
+        .await
+        .with_context(|| format!("failed to write cache file `{}`", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    async fn mock_input_server(expected_requests: u64) -> MockServer {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/2022/day/1/input"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("1000\n2000\n"))
+            .expect(expected_requests)
+            .mount(&server)
+            .await;
+
+        server
+    }
+
+    #[test]
+    fn should_key_path_by_year_day_and_resource() {
+        let cache = Cache::new("cache", Duration::ZERO, false);
+
+        assert_eq!(
+            cache.path(2022, 1, "input"),
+            Path::new("cache").join("2022").join("01").join("input")
+        );
+    }
+
+    #[tokio::test]
+    async fn should_not_share_input_between_sessions() -> anyhow::Result<()> {
+        let server = mock_input_server(2).await;
+        let dir = tempfile::tempdir()?;
+        let url: Url = format!("{}/2022/day/1/input", server.uri()).parse()?;
+        let client = Client::new();
+
+        for session in ["abc123", "def456"] {
+            let cache = Cache::new(dir.path(), Duration::ZERO, false).for_session(session);
+
+            cache
+                .get(&client, url.clone(), (2022, 1, "input"), always)
+                .await?;
+
+            assert!(cache
+                .path(2022, 1, "input")
+                .starts_with(dir.path().join(session_hash(session))));
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_serve_repeat_fetch_from_disk() -> anyhow::Result<()> {
+        let server = mock_input_server(1).await;
+        let dir = tempfile::tempdir()?;
+        let cache = Cache::new(dir.path(), Duration::ZERO, false);
+        let url: Url = format!("{}/2022/day/1/input", server.uri()).parse()?;
+        let client = Client::new();
+
+        let first = cache
+            .get(&client, url.clone(), (2022, 1, "input"), always)
+            .await?;
+        let second = cache.get(&client, url, (2022, 1, "input"), always).await?;
+
+        assert_eq!(first, "1000\n2000\n");
+        assert_eq!(second, first);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_refetch_when_refreshing_or_not_stored() -> anyhow::Result<()> {
+        let server = mock_input_server(3).await;
+        let dir = tempfile::tempdir()?;
+        let url: Url = format!("{}/2022/day/1/input", server.uri()).parse()?;
+        let client = Client::new();
+
+        let cache = Cache::new(dir.path(), Duration::ZERO, false);
+        cache
+            .get(&client, url.clone(), (2022, 1, "input"), |_| false)
+            .await?;
+        cache
+            .get(&client, url.clone(), (2022, 1, "input"), always)
+            .await?;
+
+        let cache = Cache::new(dir.path(), Duration::ZERO, true);
+        cache.get(&client, url, (2022, 1, "input"), always).await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_space_network_requests_by_min_interval() -> anyhow::Result<()> {
+        let server = mock_input_server(2).await;
+        let dir = tempfile::tempdir()?;
+        let cache = Cache::new(dir.path(), Duration::from_millis(300), true);
+        let url: Url = format!("{}/2022/day/1/input", server.uri()).parse()?;
+        let client = Client::new();
+
+        let start = Instant::now();
+        cache
+            .get(&client, url.clone(), (2022, 1, "input"), always)
+            .await?;
+        cache.get(&client, url, (2022, 1, "input"), always).await?;
+
+        assert!(start.elapsed() >= Duration::from_millis(300));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_let_concurrent_requests_overlap() -> anyhow::Result<()> {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/2022/day/1/input"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string("1000\n")
+                    .set_delay(Duration::from_millis(400)),
+            )
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let dir = tempfile::tempdir()?;
+        let cache = Cache::new(dir.path(), Duration::from_millis(50), true);
+        let url: Url = format!("{}/2022/day/1/input", server.uri()).parse()?;
+        let client = Client::new();
+
+        let start = Instant::now();
+        let (first, second) =
+            tokio::join!(cache.fetch(&client, url.clone()), cache.fetch(&client, url));
+        first?;
+        second?;
+
+        assert!(start.elapsed() < Duration::from_millis(800));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_serve_recent_responses_until_max_age() -> anyhow::Result<()> {
+        let server = mock_input_server(2).await;
+        let dir = tempfile::tempdir()?;
+        let cache = Cache::new(dir.path(), Duration::ZERO, false);
+        let url: Url = format!("{}/2022/day/1/input", server.uri()).parse()?;
+        let client = Client::new();
+        let path = cache.event_path(2022, "input.json");
+
+        let hour = Duration::from_secs(60 * 60);
+        cache.get_recent(&client, url.clone(), &path, hour).await?;
+        cache.get_recent(&client, url.clone(), &path, hour).await?;
+        cache
+            .get_recent(&client, url, &path, Duration::ZERO)
+            .await?;
+
+        Ok(())
+    }
+}
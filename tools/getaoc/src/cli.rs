@@ -1,34 +1,191 @@
-use crate::date::{self, Day, Year};
-use clap::Parser;
-use std::path::PathBuf;
+use crate::{
+    config::Config,
+    date::{self, Day, Year},
+    leaderboard,
+    submit::Part,
+};
+use clap::{Args, Parser, Subcommand};
+use std::{ops::RangeInclusive, path::PathBuf};
+
+/// Name of file to save puzzle input when neither a flag nor the config sets one
+const DEFAULT_INPUT_FILENAME: &str = "input";
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 pub struct Cli {
     /// Target puzzle year
-    #[arg(short, long, default_value_t = date::this_year())]
-    pub year: Year,
+    ///
+    /// [default: `year` from the config file, or the latest event]
+    #[arg(short, long, global = true)]
+    pub year: Option<Year>,
 
     /// Target puzzle day of month
-    #[arg(short, long, default_value_t = date::day_of_month())]
-    pub day: Day,
+    ///
+    /// [default: the latest unlocked puzzle of the target year, using the
+    /// puzzle release timezone (UTC-5)]
+    #[arg(short, long, global = true)]
+    pub day: Option<Day>,
+
+    /// Wait with a countdown until the puzzle unlocks instead of failing
+    #[arg(short, long, global = true)]
+    pub wait: bool,
 
     /// Directory to save output files
-    #[arg(default_value = "./")]
-    pub outdir: PathBuf,
+    ///
+    /// [default: the day's directory matching `outdir` from the config file, or ./]
+    pub outdir: Option<PathBuf>,
 
-    /// Name of file to save puzzle input
-    #[arg(short, long = "input", default_value = "input")]
-    pub input_filename: String,
+    /// Range of days to fetch into their `calendar/NN_*` crates, e.g. `1..=25`
+    #[arg(long, value_parser = date::parse_days, conflicts_with = "all")]
+    pub days: Option<RangeInclusive<Day>>,
+
+    /// Fetch every unlocked day of the event into its `calendar/NN_*` crate
+    #[arg(long)]
+    pub all: bool,
+
+    /// Maximum number of days fetched at the same time
+    #[arg(long, default_value_t = 4)]
+    pub concurrency: usize,
+
+    /// Directory containing the day crates and their `template`
+    #[arg(short, long, global = true, default_value = "calendar")]
+    pub calendar: PathBuf,
+
+    #[command(flatten)]
+    pub output: OutputArgs,
 
     /// Session id used for authentication.
     ///
     /// This can be collected by logging into https://adventofcode.com/
     /// and inspecting the session cookie.
-    #[arg(short, long, env, hide_env_values = true)]
-    pub session: String,
+    #[arg(short, long, env, hide_env_values = true, global = true)]
+    pub session: Option<String>,
+
+    /// Name of the config file profile to take the session id from
+    #[arg(long, env = "GETAOC_PROFILE", global = true)]
+    pub profile: Option<String>,
+
+    /// Directory to cache responses in
+    ///
+    /// [default: $XDG_CACHE_HOME/getaoc]
+    #[arg(long, global = true)]
+    pub cache_dir: Option<PathBuf>,
+
+    /// Fetch fresh responses instead of serving them from the cache
+    #[arg(long, global = true)]
+    pub refresh: bool,
+
+    /// Minimum number of seconds between network requests
+    #[arg(long, global = true, default_value_t = 5)]
+    pub min_interval: u64,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// Names of the files that puzzle data is saved to
+#[derive(Args, Debug)]
+pub struct OutputArgs {
+    /// Name of file to save puzzle input
+    ///
+    /// [default: `input` from the config file, or input]
+    #[arg(short, long = "input")]
+    pub input_filename: Option<String>,
+
+    /// Name of file to save the puzzle description as Markdown
+    #[arg(long = "puzzle", default_value = "puzzle.md")]
+    pub puzzle_filename: String,
+
+    /// Name of file to save the puzzle's example input
+    #[arg(short, long = "example", default_value = "basic_input")]
+    pub example_filename: String,
+
+    /// Pick the example from the `<pre><code>` block at this index instead
+    /// of the first block following "For example"
+    #[arg(long)]
+    pub example_index: Option<usize>,
+}
+
+impl OutputArgs {
+    /// Fill in file names that were not passed as flags from `config`
+    pub fn apply_config(&mut self, config: &Config) {
+        if self.input_filename.is_none() {
+            self.input_filename = config.input.clone();
+        }
+    }
+
+    /// Name of file to save puzzle input
+    pub fn input_filename(&self) -> &str {
+        self.input_filename
+            .as_deref()
+            .unwrap_or(DEFAULT_INPUT_FILENAME)
+    }
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Submit an answer for one part of the puzzle
+    Submit(SubmitArgs),
+
+    /// Create a new day crate from the calendar template and fetch its input
+    New(NewArgs),
+
+    /// Print a private leaderboard ranked by local score
+    Leaderboard(LeaderboardArgs),
+
+    /// Show the stars collected for every day crate in the calendar
+    Status,
+
+    /// Check that the session is valid and show who it belongs to
+    Whoami,
+}
+
+#[derive(Args, Debug)]
+pub struct SubmitArgs {
+    /// Puzzle part the answer is for
+    #[arg(short, long, value_enum)]
+    pub part: Part,
+
+    /// Answer to submit
+    pub answer: String,
+
+    /// Answer store that correct answers are recorded in
+    #[arg(long, default_value = "answers.toml")]
+    pub answers: PathBuf,
+}
+
+#[derive(Args, Debug)]
+pub struct NewArgs {
+    /// Name of the puzzle, used for the crate directory and package name
+    #[arg(short, long)]
+    pub name: String,
+
+    #[command(flatten)]
+    pub output: OutputArgs,
+}
+
+#[derive(Args, Debug)]
+pub struct LeaderboardArgs {
+    /// Id of the private leaderboard, the number at the end of its url
+    #[arg(long)]
+    pub id: u64,
+
+    /// Format to print the leaderboard in
+    #[arg(short, long, value_enum, default_value_t)]
+    pub format: leaderboard::Format,
 }
 
 pub fn parse_args() -> Cli {
     Cli::parse()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn should_have_valid_arguments() {
+        Cli::command().debug_assert();
+    }
+}
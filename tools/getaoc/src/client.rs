@@ -0,0 +1,514 @@
+//! # Client
+//!
+//! Module with [`AocClient`], the library interface for fetching puzzles and
+//! submitting answers without going through the command-line
+
+use std::{
+    collections::BTreeMap,
+    env,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use ::url::Url;
+use anyhow::Context;
+use chrono::{DateTime, FixedOffset};
+use reqwest::Client;
+use tokio::fs;
+
+use crate::{
+    cache::{self, Cache},
+    create_client,
+    date::{self, Day, Year},
+    error::Error,
+    leaderboard::{self, Leaderboard},
+    puzzle::{self, Puzzle},
+    status,
+    submit::{self, Outcome, Part},
+    url::{self, base_url},
+    whoami::{self, User},
+};
+
+/// Environment variable the session id is read from by [`AocClient::from_env`]
+pub const SESSION_VAR: &str = "SESSION";
+
+/// Default minimum time between two network requests
+pub const DEFAULT_MIN_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Client for adventofcode.com that caches responses and rate limits requests
+///
+/// Methods for a single puzzle check that it is unlocked before making a request.
+#[derive(Debug)]
+pub struct AocClient {
+    client: Client,
+    cache: Cache,
+    base_url: Url,
+    /// file recording when the session was first seen
+    first_seen_path: PathBuf,
+}
+
+impl AocClient {
+    /// Create a client for `base_url` that stores responses in `cache`, in a
+    /// directory of their own for `session`
+    pub fn new(session: &str, base_url: Url, cache: Cache) -> anyhow::Result<Self> {
+        let client = create_client(session, &base_url)?;
+        let first_seen_path = whoami::first_seen_path(cache.dir(), session);
+
+        Ok(Self {
+            client,
+            cache: cache.for_session(session),
+            base_url,
+            first_seen_path,
+        })
+    }
+
+    /// Create a client for adventofcode.com using the default cache directory
+    pub fn with_session(session: &str) -> anyhow::Result<Self> {
+        let cache = Cache::new(Cache::default_dir(), DEFAULT_MIN_INTERVAL, false);
+
+        Self::new(session, base_url()?, cache)
+    }
+
+    /// Create a client with the session id from the `SESSION` environment
+    /// variable, also looking in a `.env` file
+    pub fn from_env() -> anyhow::Result<Self> {
+        dotenvy::dotenv().ok();
+
+        let session = env::var(SESSION_VAR)
+            .with_context(|| format!("missing session id; set the `{SESSION_VAR}` variable"))?;
+
+        Self::with_session(&session)
+    }
+
+    /// Cache responses are stored in
+    pub fn cache(&self) -> &Cache {
+        &self.cache
+    }
+
+    /// Get the puzzle input for `year` and `day`
+    pub async fn input(&self, year: Year, day: Day) -> anyhow::Result<String> {
+        date::check_unlocked(year, day, date::now())?;
+
+        let input_url = url::build_input_url(&self.base_url, year, day)?;
+
+        self.cache
+            .get(&self.client, input_url, (year, day, "input"), cache::always)
+            .await
+    }
+
+    /// Get the puzzle description page for `year` and `day`
+    ///
+    /// The page is only cached once both parts are unlocked.
+    pub async fn puzzle(&self, year: Year, day: Day) -> anyhow::Result<Puzzle> {
+        date::check_unlocked(year, day, date::now())?;
+
+        let puzzle_url = url::build_puzzle_url(&self.base_url, year, day)?;
+
+        let html = self
+            .cache
+            .get(
+                &self.client,
+                puzzle_url.clone(),
+                (year, day, "puzzle.html"),
+                puzzle::is_complete,
+            )
+            .await?;
+
+        Ok(Puzzle::new(puzzle_url, html))
+    }
+
+    /// Submit `answer` for `part` of the puzzle for `year` and `day`
+    pub async fn submit(
+        &self,
+        year: Year,
+        day: Day,
+        part: Part,
+        answer: &str,
+    ) -> anyhow::Result<Outcome> {
+        date::check_unlocked(year, day, date::now())?;
+
+        let answer_url = url::build_answer_url(&self.base_url, year, day)?;
+        self.cache.throttle().await?;
+
+        submit::submit_answer(&self.client, answer_url, part, answer).await
+    }
+
+    /// Get the private leaderboard with `id` for the `year` event
+    ///
+    /// Responses are reused for 15 minutes, so the leaderboard is not
+    /// requested more often than adventofcode.com allows.
+    pub async fn leaderboard(&self, year: Year, id: u64) -> anyhow::Result<Leaderboard> {
+        let leaderboard_url = url::build_leaderboard_url(&self.base_url, year, id)?;
+        let path = self
+            .cache
+            .event_path(year, &format!("leaderboard-{id}.json"));
+
+        let json = self
+            .cache
+            .get_recent(
+                &self.client,
+                leaderboard_url,
+                &path,
+                Duration::from_secs(leaderboard::MAX_AGE_SECS),
+            )
+            .await?;
+
+        Ok(serde_json::from_str(&json)?)
+    }
+
+    /// Get the number of stars collected for each day of the `year` event
+    ///
+    /// The calendar changes with every star, so it is never cached.
+    pub async fn stars(&self, year: Year) -> anyhow::Result<BTreeMap<Day, u8>> {
+        let calendar_url = url::build_calendar_url(&self.base_url, year)?;
+        let html = self.cache.fetch(&self.client, calendar_url).await?;
+
+        Ok(status::parse_stars(&html))
+    }
+
+    /// Get the user the session belongs to
+    ///
+    /// Fails with [`Error::Unauthorized`] if the session is invalid or expired.
+    pub async fn whoami(&self) -> anyhow::Result<User> {
+        let events_url = url::build_events_url(&self.base_url)?;
+        let html = self.cache.fetch(&self.client, events_url).await?;
+
+        let user = whoami::parse_user(&html).ok_or(Error::Unauthorized)?;
+
+        Ok(User {
+            first_seen: Some(self.session_first_seen().await?),
+            ..user
+        })
+    }
+
+    /// Time this session was first used with a valid response from [`whoami`]
+    ///
+    /// [`whoami`]: AocClient::whoami
+    pub async fn session_first_seen(&self) -> anyhow::Result<DateTime<FixedOffset>> {
+        whoami::first_seen(&self.first_seen_path, date::now()).await
+    }
+
+    /// Read the puzzle input from `path`, fetching and saving it there first
+    /// if the file does not exist yet
+    pub async fn load_input<P: AsRef<Path>>(
+        &self,
+        path: P,
+        year: Year,
+        day: Day,
+    ) -> anyhow::Result<String> {
+        let path = path.as_ref();
+
+        if fs::try_exists(path).await? {
+            return Ok(fs::read_to_string(path).await?);
+        }
+
+        let input = self.input(year, day).await?;
+        crate::write_file(&path, &input).await?;
+
+        Ok(input)
+    }
+}
+
+/// Module with a blocking [`AocClient`](blocking::AocClient)
+pub mod blocking {
+    use std::{collections::BTreeMap, path::Path};
+
+    use tokio::runtime::{self, Runtime};
+
+    use crate::{
+        date::{Day, Year},
+        leaderboard::Leaderboard,
+        puzzle::Puzzle,
+        submit::{Outcome, Part},
+        whoami::User,
+    };
+
+    /// Blocking wrapper around [`AocClient`](super::AocClient)
+    ///
+    /// Each call blocks the current thread on a private runtime, so it must
+    /// not be used from within an async context.
+    #[derive(Debug)]
+    pub struct AocClient {
+        inner: super::AocClient,
+        runtime: Runtime,
+    }
+
+    impl AocClient {
+        /// Wrap an async client
+        pub fn new(inner: super::AocClient) -> anyhow::Result<Self> {
+            let runtime = runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()?;
+
+            Ok(Self { inner, runtime })
+        }
+
+        /// See [`AocClient::with_session`](super::AocClient::with_session)
+        pub fn with_session(session: &str) -> anyhow::Result<Self> {
+            Self::new(super::AocClient::with_session(session)?)
+        }
+
+        /// See [`AocClient::from_env`](super::AocClient::from_env)
+        pub fn from_env() -> anyhow::Result<Self> {
+            Self::new(super::AocClient::from_env()?)
+        }
+
+        /// See [`AocClient::input`](super::AocClient::input)
+        pub fn input(&self, year: Year, day: Day) -> anyhow::Result<String> {
+            self.runtime.block_on(self.inner.input(year, day))
+        }
+
+        /// See [`AocClient::puzzle`](super::AocClient::puzzle)
+        pub fn puzzle(&self, year: Year, day: Day) -> anyhow::Result<Puzzle> {
+            self.runtime.block_on(self.inner.puzzle(year, day))
+        }
+
+        /// See [`AocClient::submit`](super::AocClient::submit)
+        pub fn submit(
+            &self,
+            year: Year,
+            day: Day,
+            part: Part,
+            answer: &str,
+        ) -> anyhow::Result<Outcome> {
+            self.runtime
+                .block_on(self.inner.submit(year, day, part, answer))
+        }
+
+        /// See [`AocClient::leaderboard`](super::AocClient::leaderboard)
+        pub fn leaderboard(&self, year: Year, id: u64) -> anyhow::Result<Leaderboard> {
+            self.runtime.block_on(self.inner.leaderboard(year, id))
+        }
+
+        /// See [`AocClient::stars`](super::AocClient::stars)
+        pub fn stars(&self, year: Year) -> anyhow::Result<BTreeMap<Day, u8>> {
+            self.runtime.block_on(self.inner.stars(year))
+        }
+
+        /// See [`AocClient::whoami`](super::AocClient::whoami)
+        pub fn whoami(&self) -> anyhow::Result<User> {
+            self.runtime.block_on(self.inner.whoami())
+        }
+
+        /// See [`AocClient::load_input`](super::AocClient::load_input)
+        pub fn load_input<P: AsRef<Path>>(
+            &self,
+            path: P,
+            year: Year,
+            day: Day,
+        ) -> anyhow::Result<String> {
+            self.runtime
+                .block_on(self.inner.load_input(path, year, day))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::runtime::Runtime;
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    fn client(server: &MockServer, cache_dir: &Path) -> anyhow::Result<AocClient> {
+        let cache = Cache::new(cache_dir, Duration::ZERO, false);
+
+        AocClient::new("abc123", server.uri().parse()?, cache)
+    }
+
+    #[tokio::test]
+    async fn should_fetch_input_once_and_cache_it() -> anyhow::Result<()> {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/2022/day/1/input"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("1000\n2000\n"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let cache_dir = tempfile::tempdir()?;
+        let aoc = client(&server, cache_dir.path())?;
+
+        assert_eq!(aoc.input(2022, 1).await?, "1000\n2000\n");
+        assert_eq!(aoc.input(2022, 1).await?, "1000\n2000\n");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_load_missing_input_into_file() -> anyhow::Result<()> {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/2022/day/1/input"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("1000\n"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let cache_dir = tempfile::tempdir()?;
+        let outdir = tempfile::tempdir()?;
+        let aoc = client(&server, cache_dir.path())?;
+        let input_path = outdir.path().join("input");
+
+        assert_eq!(aoc.load_input(&input_path, 2022, 1).await?, "1000\n");
+        assert_eq!(std::fs::read_to_string(&input_path)?, "1000\n");
+
+        std::fs::write(&input_path, "edited\n")?;
+        assert_eq!(aoc.load_input(&input_path, 2022, 1).await?, "edited\n");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_reject_locked_puzzles_without_a_request() -> anyhow::Result<()> {
+        let server = MockServer::start().await;
+        let cache_dir = tempfile::tempdir()?;
+        let aoc = client(&server, cache_dir.path())?;
+
+        let result = aoc.input(2022, 26).await;
+
+        assert!(result.is_err());
+        assert!(server
+            .received_requests()
+            .await
+            .unwrap_or_default()
+            .is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_fetch_leaderboard_once_per_max_age() -> anyhow::Result<()> {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/2022/leaderboard/private/view/1001.json"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(include_str!("../fixtures/leaderboard.json")),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let cache_dir = tempfile::tempdir()?;
+        let aoc = client(&server, cache_dir.path())?;
+
+        let first = aoc.leaderboard(2022, 1001).await?;
+        let second = aoc.leaderboard(2022, 1001).await?;
+
+        assert_eq!(first.owner_id, 1001);
+        assert_eq!(second, first);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_fetch_stars_without_caching_them() -> anyhow::Result<()> {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/2022"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string("<a class=\"calendar-day1 calendar-verycomplete\">1</a>"),
+            )
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let cache_dir = tempfile::tempdir()?;
+        let aoc = client(&server, cache_dir.path())?;
+
+        for _ in 0..2 {
+            assert_eq!(aoc.stars(2022).await?, BTreeMap::from([(1, 2)]));
+        }
+        assert!(!aoc.cache().event_path(2022, "").exists());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_return_first_seen_time_from_whoami() -> anyhow::Result<()> {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/events"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                "<header><div class=\"user\">alice <span class=\"star-count\">44*</span></div></header>",
+            ))
+            .mount(&server)
+            .await;
+
+        let cache_dir = tempfile::tempdir()?;
+        let aoc = client(&server, cache_dir.path())?;
+
+        let first = aoc.whoami().await?;
+        let second = aoc.whoami().await?;
+
+        assert_eq!(first.name, "alice");
+        assert!(first.first_seen.is_some());
+        assert_eq!(second.first_seen, first.first_seen);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_reject_invalid_session_in_whoami() -> anyhow::Result<()> {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/events"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                "<header><nav><a href=\"/auth/login\">[Log In]</a></nav></header>",
+            ))
+            .mount(&server)
+            .await;
+
+        let cache_dir = tempfile::tempdir()?;
+        let aoc = client(&server, cache_dir.path())?;
+
+        let result = aoc.whoami().await;
+
+        assert!(matches!(
+            result.unwrap_err().downcast_ref::<Error>(),
+            Some(Error::Unauthorized)
+        ));
+        assert!(!aoc.first_seen_path.exists());
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_fetch_puzzle_with_blocking_client() -> anyhow::Result<()> {
+        let runtime = Runtime::new()?;
+        let server = runtime.block_on(MockServer::start());
+
+        runtime.block_on(
+            Mock::given(method("GET"))
+                .and(path("/2022/day/1"))
+                .respond_with(ResponseTemplate::new(200).set_body_string(
+                    "<article class=\"day-desc\"><h2>--- Day 1: Calorie Counting ---</h2>\
+                    <p>For example:</p><pre><code>1000\n</code></pre></article>",
+                ))
+                .mount(&server),
+        );
+
+        let cache_dir = tempfile::tempdir()?;
+        let aoc = blocking::AocClient::new(client(&server, cache_dir.path())?)?;
+        let puzzle = aoc.puzzle(2022, 1)?;
+
+        assert_eq!(
+            puzzle.markdown(),
+            "## Day 1: Calorie Counting\n\nFor example:\n\n```\n1000\n```\n"
+        );
+        assert_eq!(puzzle.example(None).as_deref(), Some("1000\n"));
+        assert!(!puzzle.is_complete());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,419 @@
+//! # Config
+//!
+//! Module for the optional TOML configuration file. It is read from
+//! `$XDG_CONFIG_HOME/getaoc/config.toml` and from a `getaoc.toml` in the
+//! current directory or one of its parents, usually the workspace root.
+//! Values in the workspace file take precedence over the user's file, and
+//! command-line flags take precedence over both. The workspace file is meant
+//! to be committed, so session ids are only read from the user's file:
+//!
+//! ```toml
+//! year = 2022
+//! outdir = "calendar/{day:02}_{slug}"
+//! input = "input"
+//! profile = "alice"
+//!
+//! [profiles.alice]
+//! session = "53616c7465645f5f..."
+//!
+//! [profiles.bob]
+//! session = "53616c7465645f5f..."
+//! ```
+
+use std::{
+    collections::BTreeMap,
+    env,
+    error::Error,
+    fmt::{self, Display, Formatter},
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use serde::Deserialize;
+use tokio::fs;
+
+use crate::date::{Day, Year};
+
+/// Name of the configuration file in the user's config directory
+const USER_CONFIG_FILE: &str = "config.toml";
+
+/// Name of the configuration file looked up from the current directory upwards
+const WORKSPACE_CONFIG_FILE: &str = "getaoc.toml";
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// Default puzzle year
+    pub year: Option<Year>,
+    /// Pattern for day directories, see [`OutdirPattern`]
+    pub outdir: Option<String>,
+    /// Default name of the file to save puzzle input
+    pub input: Option<String>,
+    /// Session id used when no profile is selected
+    pub session: Option<String>,
+    /// Name of the profile used when `--profile` is not passed
+    pub profile: Option<String>,
+    /// Named session profiles
+    #[serde(default)]
+    pub profiles: BTreeMap<String, Profile>,
+}
+
+/// Named set of credentials, for machines shared by several people
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Profile {
+    pub session: String,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ConfigError {
+    UnknownProfile(String),
+    MissingSession,
+    SessionInWorkspace(PathBuf),
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::UnknownProfile(name) => {
+                write!(
+                    f,
+                    "ConfigError: no profile named `{name}` in the config file"
+                )
+            }
+            ConfigError::MissingSession => write!(
+                f,
+                "ConfigError: missing session id; pass `--session`, set the `SESSION` \
+                environment variable or add a profile to the config file"
+            ),
+            ConfigError::SessionInWorkspace(path) => write!(
+                f,
+                "ConfigError: `{}` may be committed, so it can't hold session ids; \
+                move `session` and `[profiles]` to the user's config file",
+                path.display()
+            ),
+        }
+    }
+}
+
+impl Error for ConfigError {}
+
+impl Config {
+    /// Path of the configuration file in the user's config directory
+    pub fn user_path() -> Option<PathBuf> {
+        Some(
+            dirs::config_dir()?
+                .join(env!("CARGO_PKG_NAME"))
+                .join(USER_CONFIG_FILE),
+        )
+    }
+
+    /// Find `getaoc.toml` in `dir` or the closest parent that has one
+    pub async fn find_workspace_path(dir: &Path) -> Option<PathBuf> {
+        for dir in dir.ancestors() {
+            let path = dir.join(WORKSPACE_CONFIG_FILE);
+
+            if fs::try_exists(&path).await.unwrap_or_default() {
+                return Some(path);
+            }
+        }
+
+        None
+    }
+
+    /// Load and merge the user and workspace configuration files, either of
+    /// which may be missing
+    pub async fn load() -> anyhow::Result<Self> {
+        let mut config = Config::default();
+
+        if let Some(path) = Self::user_path() {
+            config = config.merge(Self::read(&path).await?.unwrap_or_default());
+        }
+
+        if let Some(path) = Self::find_workspace_path(&env::current_dir()?).await {
+            let workspace = Self::read(&path).await?.unwrap_or_default();
+            workspace.check_no_sessions(&path)?;
+
+            config = config.merge(workspace);
+        }
+
+        Ok(config)
+    }
+
+    /// Read the configuration file at `path`, if it exists
+    pub async fn read(path: &Path) -> anyhow::Result<Option<Self>> {
+        if !fs::try_exists(path).await? {
+            return Ok(None);
+        }
+
+        let text = fs::read_to_string(path)
+            .await
+            .with_context(|| format!("failed to read config file `{}`", path.display()))?;
+
+        let config = toml::from_str(&text)
+            .with_context(|| format!("failed to parse config file `{}`", path.display()))?;
+
+        Ok(Some(config))
+    }
+
+    /// Check that a workspace configuration read from `path` holds no session
+    /// ids, since it may be committed
+    pub fn check_no_sessions(&self, path: &Path) -> Result<(), ConfigError> {
+        if self.session.is_some() || !self.profiles.is_empty() {
+            return Err(ConfigError::SessionInWorkspace(path.to_path_buf()));
+        }
+
+        Ok(())
+    }
+
+    /// Combine two configurations, preferring values from `other`
+    pub fn merge(mut self, other: Config) -> Self {
+        self.profiles.extend(other.profiles);
+
+        Self {
+            year: other.year.or(self.year),
+            outdir: other.outdir.or(self.outdir),
+            input: other.input.or(self.input),
+            session: other.session.or(self.session),
+            profile: other.profile.or(self.profile),
+            profiles: self.profiles,
+        }
+    }
+
+    /// Pick the session id to use
+    ///
+    /// An explicitly selected `profile` comes first, then the `session` passed
+    /// on the command line or through the environment, then the default
+    /// profile and finally the top-level `session` of the config file.
+    pub fn session(
+        &self,
+        profile: Option<&str>,
+        session: Option<String>,
+    ) -> Result<String, ConfigError> {
+        let profile_session = |name: &str| {
+            self.profiles
+                .get(name)
+                .map(|profile| profile.session.clone())
+                .ok_or_else(|| ConfigError::UnknownProfile(name.to_string()))
+        };
+
+        if let Some(name) = profile {
+            return profile_session(name);
+        }
+
+        if let Some(session) = session {
+            return Ok(session);
+        }
+
+        if let Some(name) = &self.profile {
+            return profile_session(name);
+        }
+
+        self.session.clone().ok_or(ConfigError::MissingSession)
+    }
+
+    /// Pattern for day directories, defaulting to `NN_slug` crates in `calendar`
+    pub fn outdir_pattern(&self, calendar: &Path) -> OutdirPattern {
+        match &self.outdir {
+            Some(pattern) => OutdirPattern::new(pattern),
+            None => OutdirPattern::new(&format!("{}/{{day:02}}_{{slug}}", calendar.display())),
+        }
+    }
+}
+
+/// Path pattern for a day's directory like `calendar/{day:02}_{slug}`
+///
+/// Supports the placeholders `{year}`, `{day}`, `{day:02}` and `{slug}`. The
+/// slug is the puzzle name given to `getaoc new`, and may only appear in the
+/// last path component.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutdirPattern(String);
+
+impl OutdirPattern {
+    pub fn new(pattern: &str) -> Self {
+        Self(pattern.to_string())
+    }
+
+    /// Path of the directory for `year`, `day` and `slug`
+    pub fn render(&self, year: Year, day: Day, slug: &str) -> PathBuf {
+        PathBuf::from(self.fill(year, day).replace("{slug}", slug))
+    }
+
+    /// Find an existing directory for `year` and `day`, matching any slug
+    pub async fn find(&self, year: Year, day: Day) -> anyhow::Result<Option<PathBuf>> {
+        let path = PathBuf::from(self.fill(year, day));
+        let file_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        let Some((prefix, suffix)) = file_name.split_once("{slug}") else {
+            return Ok(fs::try_exists(&path).await?.then_some(path));
+        };
+
+        let parent = match path.parent() {
+            Some(parent) if parent.as_os_str().is_empty() => Path::new("."),
+            Some(parent) => parent,
+            None => return Ok(None),
+        };
+
+        let Ok(mut entries) = fs::read_dir(parent).await else {
+            return Ok(None);
+        };
+
+        while let Some(entry) = entries.next_entry().await? {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            let is_match = name.len() > prefix.len() + suffix.len()
+                && name.starts_with(prefix)
+                && name.ends_with(suffix);
+
+            if is_match && entry.file_type().await?.is_dir() {
+                return Ok(Some(entry.path()));
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn fill(&self, year: Year, day: Day) -> String {
+        self.0
+            .replace("{year}", &year.to_string())
+            .replace("{day:02}", &format!("{day:02}"))
+            .replace("{day}", &day.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"
+year = 2022
+outdir = "calendar/{day:02}_{slug}"
+input = "input.txt"
+profile = "alice"
+
+[profiles.alice]
+session = "alice-session"
+
+[profiles.bob]
+session = "bob-session"
+"#;
+
+    fn config() -> Config {
+        toml::from_str(CONFIG).unwrap()
+    }
+
+    #[test]
+    fn should_parse_config() {
+        let config = config();
+
+        assert_eq!(config.year, Some(2022));
+        assert_eq!(config.input.as_deref(), Some("input.txt"));
+        assert_eq!(config.profiles.len(), 2);
+    }
+
+    #[test]
+    fn should_pick_session_by_precedence() {
+        let config = config();
+
+        assert_eq!(
+            config.session(Some("bob"), Some("flag".to_string())),
+            Ok("bob-session".to_string())
+        );
+        assert_eq!(
+            config.session(None, Some("flag".to_string())),
+            Ok("flag".to_string())
+        );
+        assert_eq!(config.session(None, None), Ok("alice-session".to_string()));
+        assert_eq!(
+            config.session(Some("carol"), None),
+            Err(ConfigError::UnknownProfile("carol".to_string()))
+        );
+        assert_eq!(
+            Config::default().session(None, None),
+            Err(ConfigError::MissingSession)
+        );
+    }
+
+    #[test]
+    fn should_prefer_workspace_values_when_merging() {
+        let workspace = Config {
+            year: Some(2021),
+            profiles: BTreeMap::from([(
+                "carol".to_string(),
+                Profile {
+                    session: "carol-session".to_string(),
+                },
+            )]),
+            ..Config::default()
+        };
+
+        let merged = config().merge(workspace);
+
+        assert_eq!(merged.year, Some(2021));
+        assert_eq!(merged.input.as_deref(), Some("input.txt"));
+        assert_eq!(merged.profiles.len(), 3);
+    }
+
+    #[test]
+    fn should_reject_sessions_in_workspace_config() {
+        let path = Path::new(WORKSPACE_CONFIG_FILE);
+        let workspace = Config {
+            profile: Some("alice".to_string()),
+            ..Config::default()
+        };
+
+        assert_eq!(workspace.check_no_sessions(path), Ok(()));
+        assert_eq!(
+            config().check_no_sessions(path),
+            Err(ConfigError::SessionInWorkspace(path.to_path_buf()))
+        );
+    }
+
+    #[test]
+    fn should_default_to_padded_day_crates_in_calendar() {
+        let pattern = Config::default().outdir_pattern(Path::new("calendar"));
+
+        assert_eq!(
+            pattern.render(2022, 5, "supply_stack"),
+            Path::new("calendar").join("05_supply_stack")
+        );
+        assert_eq!(
+            pattern.render(2022, 16, "proboscidea"),
+            Path::new("calendar").join("16_proboscidea")
+        );
+    }
+
+    #[tokio::test]
+    async fn should_render_and_find_day_dirs() -> anyhow::Result<()> {
+        let root = tempfile::tempdir()?;
+        let pattern = format!("{}/{{year}}/{{day:02}}_{{slug}}", root.path().display());
+        let pattern = OutdirPattern::new(&pattern);
+
+        let dir = pattern.render(2022, 5, "supply_stack");
+        assert_eq!(dir, root.path().join("2022").join("05_supply_stack"));
+
+        std::fs::create_dir_all(&dir)?;
+        assert_eq!(pattern.find(2022, 5).await?, Some(dir));
+        assert_eq!(pattern.find(2022, 6).await?, None);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_find_workspace_config_in_parent() -> anyhow::Result<()> {
+        let root = tempfile::tempdir()?;
+        let nested = root.path().join("calendar").join("05_supply_stack");
+        std::fs::create_dir_all(&nested)?;
+        std::fs::write(root.path().join(WORKSPACE_CONFIG_FILE), CONFIG)?;
+
+        let path = Config::find_workspace_path(&nested).await;
+
+        assert_eq!(path, Some(root.path().join(WORKSPACE_CONFIG_FILE)));
+        assert_eq!(Config::read(&path.unwrap()).await?, Some(config()));
+
+        Ok(())
+    }
+}
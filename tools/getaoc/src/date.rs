@@ -1,14 +1,335 @@
-use chrono::{Datelike, Local};
+//! # Date
+//!
+//! Module for puzzle release times. Puzzles unlock at midnight in
+//! the puzzle release timezone (UTC-5), one per day from December 1st to 25th.
+
+use std::{
+    cmp::Ordering,
+    error::Error,
+    fmt::{self, Display, Formatter},
+    io::{self, Write},
+    ops::RangeInclusive,
+};
+
+use chrono::{DateTime, Datelike, FixedOffset, TimeZone, Utc};
+use tokio::time;
 
 pub type Year = u16;
 pub type Day = u8;
 
+/// Offset of the puzzle release timezone from UTC, in hours
+const RELEASE_UTC_OFFSET: i32 = -5;
+
+/// Year of the first Advent of Code event
+const FIRST_YEAR: Year = 2015;
+
+/// Days that have a puzzle
+pub const DAYS: RangeInclusive<Day> = 1..=25;
+
+fn release_timezone() -> FixedOffset {
+    FixedOffset::east_opt(RELEASE_UTC_OFFSET * 60 * 60).expect("offset should be in bounds")
+}
+
+/// Current time in the puzzle release timezone
+pub fn now() -> DateTime<FixedOffset> {
+    Utc::now().with_timezone(&release_timezone())
+}
+
+/// Convert a unix timestamp into the puzzle release timezone
+pub fn from_timestamp(timestamp: i64) -> Option<DateTime<FixedOffset>> {
+    Some(
+        Utc.timestamp_opt(timestamp, 0)
+            .single()?
+            .with_timezone(&release_timezone()),
+    )
+}
+
+/// Time the puzzle for `year` and `day` unlocks
+pub fn unlock_time(year: Year, day: Day) -> DateTime<FixedOffset> {
+    release_timezone()
+        .with_ymd_and_hms(year as i32, 12, day as u32, 0, 0, 0)
+        .single()
+        .expect("unlock time should be a valid date")
+}
+
+/// Year and day of the latest puzzle that is unlocked at `now`
+///
+/// Outside of December this is the last day of the previous year's event.
+pub fn latest_unlocked(now: DateTime<FixedOffset>) -> (Year, Day) {
+    let year = now.year() as Year;
+
+    if now.month() == 12 {
+        (year, (now.day() as Day).min(*DAYS.end()))
+    } else {
+        (year - 1, *DAYS.end())
+    }
+}
+
+/// Year of the latest event with an unlocked puzzle
 pub fn this_year() -> Year {
-    let local = Local::now();
-    local.year() as Year
+    latest_unlocked(now()).0
+}
+
+/// Day of the latest puzzle of `year` that is unlocked at `now`
+///
+/// This is the last day for past events, and the first day for events that
+/// have not started, so waiting for it waits for the event to start.
+pub fn default_day(year: Year, now: DateTime<FixedOffset>) -> Day {
+    let (latest_year, latest_day) = latest_unlocked(now);
+
+    match year.cmp(&latest_year) {
+        Ordering::Less => *DAYS.end(),
+        Ordering::Equal => latest_day,
+        Ordering::Greater => *DAYS.start(),
+    }
+}
+
+/// Parse a range of days like `1..=25`, `1..26`, `1-25` or a single day like `7`
+pub fn parse_days(s: &str) -> Result<RangeInclusive<Day>, String> {
+    let parse = |value: &str| -> Result<Day, String> {
+        value
+            .trim()
+            .parse()
+            .map_err(|_| format!("invalid day `{value}` in range `{s}`"))
+    };
+
+    let range = if let Some((start, end)) = s.split_once("..=") {
+        parse(start)?..=parse(end)?
+    } else if let Some((start, end)) = s.split_once("..") {
+        parse(start)?..=parse(end)?.saturating_sub(1)
+    } else if let Some((start, end)) = s.split_once('-') {
+        parse(start)?..=parse(end)?
+    } else {
+        let day = parse(s)?;
+        day..=day
+    };
+
+    if range.is_empty() {
+        return Err(format!(
+            "range `{s}` has no days, its end is before its start"
+        ));
+    }
+
+    for day in [range.start(), range.end()] {
+        if !DAYS.contains(day) {
+            return Err(DateError::InvalidDay(*day).to_string());
+        }
+    }
+
+    Ok(range)
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum DateError {
+    InvalidDay(Day),
+    InvalidYear(Year),
+    EventNotStarted {
+        year: Year,
+        unlocks_at: DateTime<FixedOffset>,
+    },
+    Locked {
+        year: Year,
+        day: Day,
+        unlocks_at: DateTime<FixedOffset>,
+    },
+}
+
+impl DateError {
+    /// Time the puzzle unlocks if the error is only a matter of waiting
+    pub fn unlocks_at(&self) -> Option<DateTime<FixedOffset>> {
+        match self {
+            DateError::EventNotStarted { unlocks_at, .. }
+            | DateError::Locked { unlocks_at, .. } => Some(*unlocks_at),
+            _ => None,
+        }
+    }
 }
 
-pub fn day_of_month() -> Day {
-    let local = Local::now();
-    local.day() as Day
+impl Display for DateError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            DateError::InvalidDay(day) => write!(
+                f,
+                "DateError: day `{day}` is not between {} and {}",
+                DAYS.start(),
+                DAYS.end()
+            ),
+            DateError::InvalidYear(year) => write!(
+                f,
+                "DateError: year `{year}` is before the first event in {FIRST_YEAR}"
+            ),
+            DateError::EventNotStarted { year, unlocks_at } => write!(
+                f,
+                "DateError: the {year} event has not started; it starts at {unlocks_at} \
+                (use `--wait` to wait for it)"
+            ),
+            DateError::Locked {
+                year,
+                day,
+                unlocks_at,
+            } => write!(
+                f,
+                "DateError: puzzle for {year} day {day} is locked until {unlocks_at} \
+                (use `--wait` to wait for it)"
+            ),
+        }
+    }
+}
+
+impl Error for DateError {}
+
+/// Check that the puzzle for `year` and `day` exists and is unlocked at `now`
+pub fn check_unlocked(year: Year, day: Day, now: DateTime<FixedOffset>) -> Result<(), DateError> {
+    if !DAYS.contains(&day) {
+        return Err(DateError::InvalidDay(day));
+    }
+
+    if year < FIRST_YEAR {
+        return Err(DateError::InvalidYear(year));
+    }
+
+    let event_start = unlock_time(year, *DAYS.start());
+    if now < event_start {
+        return Err(DateError::EventNotStarted {
+            year,
+            unlocks_at: event_start,
+        });
+    }
+
+    let unlocks_at = unlock_time(year, day);
+    if now < unlocks_at {
+        return Err(DateError::Locked {
+            year,
+            day,
+            unlocks_at,
+        });
+    }
+
+    Ok(())
+}
+
+/// Wait until `unlocks_at`, printing a countdown to stderr
+pub async fn wait_until(unlocks_at: DateTime<FixedOffset>) {
+    let mut stderr = io::stderr();
+
+    loop {
+        let remaining = (unlocks_at - now()).num_seconds();
+        if remaining < 0 {
+            break;
+        }
+
+        let (hours, minutes, seconds) = (remaining / 3600, remaining / 60 % 60, remaining % 60);
+        write!(stderr, "\rUnlocks in {hours:02}:{minutes:02}:{seconds:02}").ok();
+        stderr.flush().ok();
+
+        time::sleep(time::Duration::from_millis(250)).await;
+    }
+
+    writeln!(stderr).ok();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(year: i32, month: u32, day: u32, hour: u32) -> DateTime<FixedOffset> {
+        release_timezone()
+            .with_ymd_and_hms(year, month, day, hour, 0, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn should_unlock_at_midnight_utc_minus_5() {
+        let unlocks_at = unlock_time(2022, 8);
+
+        assert_eq!(
+            unlocks_at.with_timezone(&Utc),
+            Utc.with_ymd_and_hms(2022, 12, 8, 5, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn should_pick_latest_unlocked_puzzle() {
+        assert_eq!(latest_unlocked(at(2022, 12, 8, 0)), (2022, 8));
+        assert_eq!(latest_unlocked(at(2022, 12, 31, 12)), (2022, 25));
+        assert_eq!(latest_unlocked(at(2023, 10, 18, 12)), (2022, 25));
+        assert_eq!(latest_unlocked(at(2023, 11, 30, 23)), (2022, 25));
+    }
+
+    #[test]
+    fn should_default_to_latest_unlocked_day_of_year() {
+        let now = at(2022, 12, 8, 0);
+
+        assert_eq!(default_day(2022, now), 8);
+        assert_eq!(default_day(2021, now), 25);
+        assert_eq!(default_day(2023, now), 1);
+        assert_eq!(default_day(2022, at(2023, 1, 3, 12)), 25);
+    }
+
+    #[test]
+    fn should_use_release_timezone_in_the_evening_in_europe() {
+        // 2022-12-08 03:00 in UTC+1 is still the 7th in UTC-5
+        let now = Utc
+            .with_ymd_and_hms(2022, 12, 8, 2, 0, 0)
+            .unwrap()
+            .with_timezone(&release_timezone());
+
+        assert_eq!(latest_unlocked(now), (2022, 7));
+    }
+
+    #[test]
+    fn should_reject_days_outside_event() {
+        let now = at(2022, 12, 31, 0);
+
+        assert_eq!(check_unlocked(2022, 0, now), Err(DateError::InvalidDay(0)));
+        assert_eq!(
+            check_unlocked(2022, 26, now),
+            Err(DateError::InvalidDay(26))
+        );
+        assert_eq!(
+            check_unlocked(2014, 1, now),
+            Err(DateError::InvalidYear(2014))
+        );
+        assert_eq!(check_unlocked(2022, 25, now), Ok(()));
+    }
+
+    #[test]
+    fn should_parse_day_ranges() {
+        assert_eq!(parse_days("1..=25"), Ok(1..=25));
+        assert_eq!(parse_days("3..7"), Ok(3..=6));
+        assert_eq!(parse_days("10-12"), Ok(10..=12));
+        assert_eq!(parse_days("7"), Ok(7..=7));
+        assert!(parse_days("0..=25").is_err());
+        assert!(parse_days("1..=26").is_err());
+        assert!(parse_days("a..b").is_err());
+        assert_eq!(
+            parse_days("5..1"),
+            Err("range `5..1` has no days, its end is before its start".to_string())
+        );
+        assert!(parse_days("5..5").is_err());
+        assert!(parse_days("12-3").is_err());
+    }
+
+    #[test]
+    fn should_reject_locked_puzzles() {
+        let now = at(2022, 12, 8, 12);
+
+        assert_eq!(check_unlocked(2022, 8, now), Ok(()));
+        assert_eq!(
+            check_unlocked(2022, 9, now),
+            Err(DateError::Locked {
+                year: 2022,
+                day: 9,
+                unlocks_at: unlock_time(2022, 9)
+            })
+        );
+        assert_eq!(
+            check_unlocked(2023, 1, now).map_err(|err| err.unlocks_at()),
+            Err(Some(unlock_time(2023, 1)))
+        );
+        assert!(matches!(
+            check_unlocked(2023, 1, now),
+            Err(DateError::EventNotStarted { year: 2023, .. })
+        ));
+    }
 }
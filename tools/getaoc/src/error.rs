@@ -0,0 +1,155 @@
+//! # Error
+//!
+//! Module for checking adventofcode.com responses before they are used
+
+use std::{
+    error,
+    fmt::{self, Display, Formatter},
+};
+
+use reqwest::{Response, StatusCode};
+use url::Url;
+
+/// Errors returned for requests to adventofcode.com
+#[derive(Debug)]
+pub enum Error {
+    /// Session cookie is missing, invalid or expired
+    Unauthorized,
+    /// Puzzle has not been released yet
+    NotYetUnlocked(Url),
+    /// Requested page does not exist
+    NotFound(Url),
+    /// Server responded with an unexpected status
+    Status(StatusCode, Url),
+    /// Request failed before a response was received
+    Network(reqwest::Error),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Unauthorized => write!(
+                f,
+                "Error(Unauthorized): session is invalid or expired; \
+                log in again and update the session cookie"
+            ),
+            Error::NotYetUnlocked(url) => {
+                write!(f, "Error(NotYetUnlocked): `{url}` has not unlocked yet")
+            }
+            Error::NotFound(url) => write!(f, "Error(NotFound): `{url}` was not found"),
+            Error::Status(status, url) => {
+                write!(f, "Error(Status): `{url}` responded with `{status}`")
+            }
+            Error::Network(err) => write!(f, "Error(Network): request failed\n\n{err}"),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::Network(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(err: reqwest::Error) -> Self {
+        Self::Network(err)
+    }
+}
+
+/// Check a response's status and body, returning the body only if it is valid
+///
+/// Invalid sessions get a `400` asking to log in, and locked puzzles get
+/// a `404` asking not to request the page before it unlocks.
+pub async fn read_response(response: Response) -> Result<String, Error> {
+    let status = response.status();
+    let url = response.url().clone();
+    let body = response.text().await?;
+
+    match status {
+        _ if body.contains("Please log in") => Err(Error::Unauthorized),
+        StatusCode::BAD_REQUEST | StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
+            Err(Error::Unauthorized)
+        }
+        StatusCode::NOT_FOUND if body.contains("before it unlocks") => {
+            Err(Error::NotYetUnlocked(url))
+        }
+        StatusCode::NOT_FOUND => Err(Error::NotFound(url)),
+        status if !status.is_success() => Err(Error::Status(status, url)),
+        _ => Ok(body),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    async fn respond(status: u16, body: &str) -> Result<String, Error> {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/2022/day/1/input"))
+            .respond_with(ResponseTemplate::new(status).set_body_string(body))
+            .mount(&server)
+            .await;
+
+        let response = reqwest::get(format!("{}/2022/day/1/input", server.uri())).await?;
+
+        read_response(response).await
+    }
+
+    #[tokio::test]
+    async fn should_return_body_for_valid_response() {
+        let result = respond(200, "1000\n2000\n").await;
+
+        assert_eq!(result.unwrap(), "1000\n2000\n");
+    }
+
+    #[tokio::test]
+    async fn should_detect_expired_session() {
+        let result = respond(
+            400,
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n",
+        )
+        .await;
+
+        assert!(matches!(result, Err(Error::Unauthorized)));
+    }
+
+    #[tokio::test]
+    async fn should_detect_locked_puzzle() {
+        let result = respond(
+            404,
+            "Please don't repeatedly request this endpoint before it unlocks! \
+            The calendar countdown is synchronized with the server time; \
+            the link will be enabled on the calendar the instant this puzzle becomes available.\n",
+        )
+        .await;
+
+        assert!(matches!(result, Err(Error::NotYetUnlocked(_))));
+    }
+
+    #[tokio::test]
+    async fn should_detect_missing_page() {
+        let result = respond(404, "404 Not Found").await;
+
+        assert!(matches!(result, Err(Error::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn should_detect_server_errors() {
+        let result = respond(500, "").await;
+
+        assert!(matches!(
+            result,
+            Err(Error::Status(StatusCode::INTERNAL_SERVER_ERROR, _))
+        ));
+    }
+}
@@ -0,0 +1,278 @@
+//! # Leaderboard
+//!
+//! Module for private leaderboards, as returned by the leaderboard's JSON api
+
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display, Formatter, Write},
+};
+
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+use crate::date::{self, Day};
+
+/// Minimum time between two requests for the same leaderboard, as asked for by
+/// adventofcode.com
+pub const MAX_AGE_SECS: u64 = 15 * 60;
+
+/// Format a leaderboard is printed in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    #[default]
+    Table,
+    Json,
+    Csv,
+}
+
+/// Private leaderboard for one event
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Leaderboard {
+    pub event: String,
+    pub owner_id: u64,
+    pub members: BTreeMap<String, Member>,
+}
+
+/// Member of a private leaderboard
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    /// Missing for anonymous users
+    pub name: Option<String>,
+    pub stars: u32,
+    pub local_score: u64,
+    pub global_score: u64,
+    pub last_star_ts: i64,
+    /// Stars collected, keyed by day and then by part
+    #[serde(default)]
+    pub completion_day_level: BTreeMap<Day, BTreeMap<u8, Star>>,
+}
+
+/// Star collected for one part of a puzzle
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Star {
+    pub get_star_ts: i64,
+    #[serde(default)]
+    pub star_index: u64,
+}
+
+/// Member together with their position on the leaderboard
+#[derive(Debug, Serialize)]
+pub struct Ranked<'a> {
+    pub rank: usize,
+    #[serde(flatten)]
+    pub member: &'a Member,
+}
+
+impl Member {
+    /// Name shown on the leaderboard, which falls back to the member id for
+    /// anonymous users
+    pub fn display_name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("(anonymous user #{})", self.id),
+        }
+    }
+
+    /// Every star the member collected as `(day, part, star)`, in puzzle order
+    pub fn stars(&self) -> impl Iterator<Item = (Day, u8, &Star)> {
+        self.completion_day_level
+            .iter()
+            .flat_map(|(day, parts)| parts.iter().map(|(part, star)| (*day, *part, star)))
+    }
+}
+
+impl Leaderboard {
+    /// Members ordered by local score, breaking ties by who got their last
+    /// star first
+    pub fn ranked(&self) -> Vec<Ranked<'_>> {
+        let mut members: Vec<&Member> = self.members.values().collect();
+
+        members.sort_by(|a, b| {
+            b.local_score
+                .cmp(&a.local_score)
+                .then(b.stars.cmp(&a.stars))
+                .then(a.last_star_ts.cmp(&b.last_star_ts))
+                .then(a.id.cmp(&b.id))
+        });
+
+        members
+            .into_iter()
+            .enumerate()
+            .map(|(index, member)| Ranked {
+                rank: index + 1,
+                member,
+            })
+            .collect()
+    }
+
+    /// Render the leaderboard in `format`
+    pub fn render(&self, format: Format) -> anyhow::Result<String> {
+        Ok(match format {
+            Format::Table => self.to_string(),
+            Format::Json => serde_json::to_string_pretty(&self.ranked())? + "\n",
+            Format::Csv => self.to_csv(),
+        })
+    }
+
+    /// Render one row per collected star
+    ///
+    /// Members without any stars get a single row with empty star columns.
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from("rank,id,name,local_score,stars,day,part,completed_at\n");
+
+        for Ranked { rank, member } in self.ranked() {
+            let prefix = format!(
+                "{rank},{},{},{},{}",
+                member.id,
+                csv_field(&member.display_name()),
+                member.local_score,
+                member.stars
+            );
+
+            let mut stars = member.stars().peekable();
+            if stars.peek().is_none() {
+                writeln!(out, "{prefix},,,").ok();
+            }
+
+            for (day, part, star) in stars {
+                writeln!(
+                    out,
+                    "{prefix},{day},{part},{}",
+                    format_rfc3339(star.get_star_ts)
+                )
+                .ok();
+            }
+        }
+
+        out
+    }
+}
+
+impl Display for Leaderboard {
+    /// Ranked table with the time each star was collected, in the puzzle
+    /// release timezone
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{:>4}  {:>5}  {:>5}  Name", "Rank", "Score", "Stars")?;
+
+        for Ranked { rank, member } in self.ranked() {
+            writeln!(
+                f,
+                "{rank:>4}  {:>5}  {:>5}  {}",
+                member.local_score,
+                member.stars,
+                member.display_name()
+            )?;
+
+            for (day, parts) in &member.completion_day_level {
+                let times: Vec<String> = parts
+                    .values()
+                    .map(|star| format_time(star.get_star_ts))
+                    .collect();
+
+                writeln!(f, "{:>18}day {day:>2}: {}", "", times.join("  "))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn format_time(timestamp: i64) -> String {
+    date::from_timestamp(timestamp)
+        .map(|time| time.format("%m-%d %H:%M:%S").to_string())
+        .unwrap_or_default()
+}
+
+fn format_rfc3339(timestamp: i64) -> String {
+    date::from_timestamp(timestamp)
+        .map(|time| time.to_rfc3339())
+        .unwrap_or_default()
+}
+
+/// Quote a CSV field if it contains a separator, quote or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURE: &str = include_str!("../fixtures/leaderboard.json");
+
+    fn leaderboard() -> Leaderboard {
+        serde_json::from_str(FIXTURE).unwrap()
+    }
+
+    #[test]
+    fn should_parse_fixture() {
+        let leaderboard = leaderboard();
+        let alice = &leaderboard.members["1001"];
+
+        assert_eq!(leaderboard.event, "2022");
+        assert_eq!(leaderboard.members.len(), 3);
+        assert_eq!(alice.completion_day_level[&1][&2].get_star_ts, 1669871320);
+        assert_eq!(leaderboard.members["1002"].name, None);
+    }
+
+    #[test]
+    fn should_rank_by_local_score() {
+        let leaderboard = leaderboard();
+        let ranked: Vec<(usize, String)> = leaderboard
+            .ranked()
+            .into_iter()
+            .map(|ranked| (ranked.rank, ranked.member.display_name()))
+            .collect();
+
+        assert_eq!(
+            ranked,
+            vec![
+                (1, "alice".to_string()),
+                (2, "(anonymous user #1002)".to_string()),
+                (3, "carol, the third".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_render_table_with_star_times() {
+        let expected = "\
+Rank  Score  Stars  Name
+   1     17      3  alice
+                  day  1: 12-01 00:05:12  12-01 00:08:40
+                  day  2: 12-02 01:10:22
+   2     11      2  (anonymous user #1002)
+                  day  1: 12-01 00:11:40  12-01 00:20:00
+   3      0      0  carol, the third
+";
+
+        assert_eq!(leaderboard().to_string(), expected);
+    }
+
+    #[test]
+    fn should_render_csv_row_per_star() {
+        let csv = leaderboard().to_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines.len(), 7);
+        assert_eq!(lines[1], "1,1001,alice,17,3,1,1,2022-12-01T00:05:12-05:00");
+        assert_eq!(lines[6], "3,1003,\"carol, the third\",0,0,,,");
+    }
+
+    #[test]
+    fn should_render_ranked_json() -> anyhow::Result<()> {
+        let json = leaderboard().render(Format::Json)?;
+        let value: serde_json::Value = serde_json::from_str(&json)?;
+
+        assert_eq!(value[0]["rank"], 1);
+        assert_eq!(value[0]["name"], "alice");
+        assert_eq!(value[1]["completion_day_level"]["1"]["2"]["star_index"], 20);
+
+        Ok(())
+    }
+}
@@ -1,14 +1,45 @@
+mod batch;
+mod cache;
 mod cli;
+mod client;
+mod config;
 mod date;
+mod error;
+mod leaderboard;
+mod puzzle;
+mod scaffold;
+mod status;
+mod submit;
 mod url;
+mod whoami;
 
-use std::{env, path::Path};
+use std::{
+    env,
+    path::{Path, PathBuf},
+    time::Duration,
+};
 
-use crate::{cli::Cli, url::base_url};
+use crate::{
+    cli::{Cli, Command, LeaderboardArgs, NewArgs, OutputArgs, SubmitArgs},
+    config::Config,
+    url::base_url,
+};
+use ::url::Url;
 use anyhow::Context;
 use reqwest::{cookie::Jar, Client};
 use tokio::fs;
 
+pub use crate::{
+    cache::Cache,
+    client::{blocking, AocClient},
+    date::{Day, Year},
+    error::Error,
+    leaderboard::{Leaderboard, Member, Star},
+    puzzle::Puzzle,
+    submit::{Outcome, Part},
+    whoami::User,
+};
+
 const USER_AGENT: &str = concat!(
     env!("CARGO_PKG_NAME"),
     " (",
@@ -18,12 +49,11 @@ const USER_AGENT: &str = concat!(
 );
 
 /// Create a request client
-fn create_client(session: String) -> anyhow::Result<Client> {
-    let base_url = base_url()?;
+fn create_client(session: &str, base_url: &Url) -> anyhow::Result<Client> {
     let cookie = format!("session={session}");
 
     let jar = Jar::default();
-    jar.add_cookie_str(cookie.as_str(), &base_url);
+    jar.add_cookie_str(cookie.as_str(), base_url);
 
     let client = Client::builder()
         .user_agent(USER_AGENT)
@@ -39,31 +69,172 @@ pub async fn execute() -> anyhow::Result<()> {
     let Cli {
         year,
         day,
+        wait,
         outdir,
-        input_filename,
+        days,
+        all,
+        concurrency,
+        calendar,
+        mut output,
         session,
+        profile,
+        cache_dir,
+        refresh,
+        min_interval,
+        command,
     } = cli::parse_args();
 
-    let client = create_client(session)?;
+    let config = Config::load().await?;
+    let session = config.session(profile.as_deref(), session)?;
+    let year = year.or(config.year).unwrap_or_else(date::this_year);
+    let day = day.unwrap_or_else(|| date::default_day(year, date::now()));
+    let pattern = config.outdir_pattern(&calendar);
+    output.apply_config(&config);
+
+    let cache = Cache::new(
+        cache_dir.unwrap_or_else(Cache::default_dir),
+        Duration::from_secs(min_interval),
+        refresh,
+    );
+    let aoc = AocClient::new(&session, base_url()?, cache)?;
+
+    match command {
+        Some(Command::Submit(SubmitArgs {
+            part,
+            answer,
+            answers,
+        })) => {
+            ensure_unlocked(year, day, wait).await?;
+
+            let outcome = aoc.submit(year, day, part, &answer).await?;
+            println!("{outcome}");
+
+            if outcome == Outcome::Correct {
+                if submit::record_answer(&answers, year, day, part, &answer)? {
+                    println!("recorded answer in `{}`", answers.display());
+                } else {
+                    println!(
+                        "`{}` is for another year, the answer was not recorded",
+                        answers.display()
+                    );
+                }
+            }
+
+            Ok(())
+        }
+        Some(Command::New(NewArgs { name, mut output })) => {
+            ensure_unlocked(year, day, wait).await?;
+            output.apply_config(&config);
+
+            let outdir = pattern.render(year, day, &name);
+            scaffold::create_day_crate(&calendar, &outdir, &name).await?;
+            println!("created day crate at `{}`", outdir.display());
+
+            fetch_puzzle(&aoc, year, day, &outdir, &output).await?;
+
+            Ok(())
+        }
+        Some(Command::Leaderboard(LeaderboardArgs { id, format })) => {
+            let leaderboard = aoc.leaderboard(year, id).await?;
+            print!("{}", leaderboard.render(format)?);
+
+            Ok(())
+        }
+        Some(Command::Status) => {
+            let stars = aoc.stars(year).await?;
+            status::print_status(&pattern, year, &stars).await
+        }
+        Some(Command::Whoami) => {
+            let user = aoc.whoami().await?;
+            println!("logged in as {user}");
 
-    let input_url = url::build_input_url(year, day)?;
+            if let Some(first_seen) = user.first_seen {
+                println!("{}", whoami::describe_expiry(first_seen, date::now()));
+            }
 
-    let input_data = client.get(input_url).send().await?.text().await?;
-    println!("{}", input_data);
+            Ok(())
+        }
+        None => match days.or(all.then_some(date::DAYS)) {
+            Some(days) => {
+                batch::fetch_days(
+                    &aoc,
+                    year,
+                    days,
+                    &pattern,
+                    &output,
+                    concurrency,
+                    date::now(),
+                )
+                .await
+            }
+            None => {
+                ensure_unlocked(year, day, wait).await?;
 
-    write_file(&outdir.join(input_filename), input_data).await?;
+                let outdir = match (outdir, &config.outdir) {
+                    (Some(outdir), _) => outdir,
+                    (None, Some(_)) => pattern.find(year, day).await?.with_context(|| {
+                        format!("no directory for day {day} matches the configured `outdir`")
+                    })?,
+                    (None, None) => PathBuf::from("./"),
+                };
+
+                let input = fetch_puzzle(&aoc, year, day, &outdir, &output).await?;
+                println!("{input}");
+
+                Ok(())
+            }
+        },
+    }
+}
+
+/// Check that the puzzle is unlocked, waiting for it to unlock when `wait` is set
+async fn ensure_unlocked(year: Year, day: Day, wait: bool) -> anyhow::Result<()> {
+    if let Err(err) = date::check_unlocked(year, day, date::now()) {
+        match err.unlocks_at() {
+            Some(unlocks_at) if wait => {
+                eprintln!("{err}");
+                date::wait_until(unlocks_at).await;
+            }
+            _ => return Err(err.into()),
+        }
+    }
 
     Ok(())
 }
 
+/// Fetch a puzzle's input, description and example input and save them into `outdir`
+///
+/// Returns the puzzle input
+async fn fetch_puzzle(
+    aoc: &AocClient,
+    year: Year,
+    day: Day,
+    outdir: &Path,
+    output: &OutputArgs,
+) -> anyhow::Result<String> {
+    let input_data = aoc.input(year, day).await?;
+
+    write_file(&outdir.join(output.input_filename()), &input_data).await?;
+
+    let puzzle = aoc.puzzle(year, day).await?;
+
+    write_file(&outdir.join(&output.puzzle_filename), puzzle.markdown()).await?;
+
+    match puzzle.example(output.example_index) {
+        Some(example) => write_file(&outdir.join(&output.example_filename), example).await?,
+        None => eprintln!("no example input found in puzzle description"),
+    }
+
+    Ok(input_data)
+}
+
 async fn write_file<P: AsRef<Path>, D: AsRef<[u8]>>(path: &P, data: D) -> anyhow::Result<()> {
-    let result = fs::write(path, &data).await.with_context(|| {
+    fs::write(path, &data).await.with_context(|| {
         format!(
             "failed to write to file at path `{}`",
             path.as_ref().display()
         )
-    })?;
-    Ok(result)
+    })
 }
 
 /// Module with synchronous entrypoint
@@ -78,13 +249,87 @@ pub mod sync {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use dotenvy::dotenv;
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
 
     #[tokio::test]
     async fn should_save_input() -> anyhow::Result<()> {
-        dotenv().expect(".env file not found");
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/2022/day/1/input"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("1000\n2000\n"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/2022/day/1"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                "<article class=\"day-desc\"><h2>--- Day 1: Calorie Counting ---</h2>\
+                <p>For example:</p><pre><code>1000\n</code></pre></article>",
+            ))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let cache_dir = tempfile::tempdir()?;
+        let cache = Cache::new(cache_dir.path(), Duration::ZERO, false);
+        let aoc = AocClient::new("abc123", server.uri().parse()?, cache)?;
+        let outdir = tempfile::tempdir()?;
+        let output = OutputArgs {
+            input_filename: None,
+            puzzle_filename: "puzzle.md".to_string(),
+            example_filename: "basic_input".to_string(),
+            example_index: None,
+        };
+
+        for _ in 0..2 {
+            fetch_puzzle(&aoc, 2022, 1, outdir.path(), &output).await?;
+        }
+
+        let read = |name: &str| std::fs::read_to_string(outdir.path().join(name));
+
+        assert_eq!(read("input")?, "1000\n2000\n");
+        assert_eq!(read("basic_input")?, "1000\n");
+        assert!(read("puzzle.md")?.starts_with("## Day 1: Calorie Counting"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_not_save_input_for_expired_session() -> anyhow::Result<()> {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/2022/day/1/input"))
+            .respond_with(ResponseTemplate::new(400).set_body_string(
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n",
+            ))
+            .mount(&server)
+            .await;
+
+        let cache_dir = tempfile::tempdir()?;
+        let cache = Cache::new(cache_dir.path(), Duration::ZERO, false);
+        let aoc = AocClient::new("expired", server.uri().parse()?, cache)?;
+        let outdir = tempfile::tempdir()?;
+        let output = OutputArgs {
+            input_filename: None,
+            puzzle_filename: "puzzle.md".to_string(),
+            example_filename: "basic_input".to_string(),
+            example_index: None,
+        };
+
+        let result = fetch_puzzle(&aoc, 2022, 1, outdir.path(), &output).await;
 
-        execute().await?;
+        assert!(matches!(
+            result.unwrap_err().downcast_ref::<Error>(),
+            Some(Error::Unauthorized)
+        ));
+        assert!(!outdir.path().join("input").exists());
+        assert!(!aoc.cache().path(2022, 1, "input").exists());
 
         Ok(())
     }
@@ -0,0 +1,291 @@
+//! # Puzzle
+//!
+//! Module for converting the puzzle description page into Markdown and
+//! extracting its example input
+
+use scraper::{node::Node, ElementRef, Html, Selector};
+use url::Url;
+
+/// Convert every `<article class="day-desc">` block in the puzzle page into Markdown
+///
+/// Part 2 is only present on the page once it is unlocked, so the output
+/// will contain it as soon as it is available.
+pub fn to_markdown(html: &str, page_url: &Url) -> String {
+    let document = Html::parse_document(html);
+    let selector = Selector::parse("article.day-desc").expect("selector should be valid");
+
+    let articles: Vec<String> = document
+        .select(&selector)
+        .map(|article| {
+            let mut out = String::new();
+            write_blocks(&mut out, article, page_url);
+            out.trim().to_string()
+        })
+        .collect();
+
+    let mut markdown = articles.join("\n\n");
+    markdown.push('\n');
+    markdown
+}
+
+/// Check if the puzzle page contains both parts, meaning it will not change anymore
+pub fn is_complete(html: &str) -> bool {
+    let document = Html::parse_document(html);
+    let selector = Selector::parse("article.day-desc").expect("selector should be valid");
+
+    document.select(&selector).count() == 2
+}
+
+/// Find the example input in the puzzle page
+///
+/// Without an `index`, this is the first `<pre><code>` block that follows the
+/// text "For example", falling back to the first block on the page. With an
+/// `index`, the block at that (zero-based) position is picked instead.
+pub fn extract_example(html: &str, index: Option<usize>) -> Option<String> {
+    let document = Html::parse_document(html);
+    let selector = Selector::parse("article.day-desc").expect("selector should be valid");
+
+    let mut blocks = vec![];
+    let mut example_block = None;
+    let mut seen_example_text = false;
+
+    for article in document.select(&selector) {
+        for node in article.descendants() {
+            match node.value() {
+                Node::Text(text) if text.to_lowercase().contains("for example") => {
+                    seen_example_text = true;
+                }
+                Node::Element(element) if element.name() == "pre" => {
+                    let Some(code) = node
+                        .children()
+                        .filter_map(ElementRef::wrap)
+                        .find(|child| child.value().name() == "code")
+                    else {
+                        continue;
+                    };
+
+                    if seen_example_text && example_block.is_none() {
+                        example_block = Some(blocks.len());
+                    }
+
+                    blocks.push(code.text().collect::<String>());
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let index = index.or(example_block).unwrap_or_default();
+
+    blocks.into_iter().nth(index)
+}
+
+/// Puzzle description page as returned by adventofcode.com
+#[derive(Debug, Clone)]
+pub struct Puzzle {
+    url: Url,
+    html: String,
+}
+
+impl Puzzle {
+    pub fn new(url: Url, html: String) -> Self {
+        Self { url, html }
+    }
+
+    /// Url the page was fetched from, used to resolve relative links
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+
+    /// Raw HTML of the page
+    pub fn html(&self) -> &str {
+        &self.html
+    }
+
+    /// Puzzle description as Markdown, see [`to_markdown`]
+    pub fn markdown(&self) -> String {
+        to_markdown(&self.html, &self.url)
+    }
+
+    /// Example input from the description, see [`extract_example`]
+    pub fn example(&self, index: Option<usize>) -> Option<String> {
+        extract_example(&self.html, index)
+    }
+
+    /// Check if both parts are unlocked, see [`is_complete`]
+    pub fn is_complete(&self) -> bool {
+        is_complete(&self.html)
+    }
+}
+
+/// Write the block level children of `element`
+fn write_blocks(out: &mut String, element: ElementRef, page_url: &Url) {
+    for child in element.children() {
+        let Some(child) = ElementRef::wrap(child) else {
+            continue;
+        };
+
+        match child.value().name() {
+            "h2" => {
+                let title: String = child.text().collect();
+                let title = title.trim().trim_matches('-').trim();
+                out.push_str(&format!("## {title}\n\n"));
+            }
+            "p" => {
+                write_inline(out, child, page_url);
+                out.push_str("\n\n");
+            }
+            "pre" => {
+                let code: String = child.text().collect();
+                out.push_str("```\n");
+                out.push_str(&code);
+                if !code.ends_with('\n') {
+                    out.push('\n');
+                }
+                out.push_str("```\n\n");
+            }
+            "ul" | "ol" => {
+                for item in child.children().filter_map(ElementRef::wrap) {
+                    out.push_str("- ");
+                    write_inline(out, item, page_url);
+                    out.push('\n');
+                }
+                out.push('\n');
+            }
+            _ => write_blocks(out, child, page_url),
+        }
+    }
+}
+
+/// Write the inline content of `element`, converting emphasis, code and links
+fn write_inline(out: &mut String, element: ElementRef, page_url: &Url) {
+    for child in element.children() {
+        match child.value() {
+            Node::Text(text) => out.push_str(&text.replace('\n', " ")),
+            Node::Element(_) => {
+                let child = ElementRef::wrap(child).expect("node should be an element");
+
+                match child.value().name() {
+                    "em" => {
+                        out.push('*');
+                        write_inline(out, child, page_url);
+                        out.push('*');
+                    }
+                    "code" => {
+                        let code: String = child.text().collect();
+                        out.push_str(&format!("`{code}`"));
+                    }
+                    "a" => {
+                        let href = child.value().attr("href").unwrap_or_default();
+                        let href = page_url
+                            .join(href)
+                            .map(|url| url.to_string())
+                            .unwrap_or_else(|_| href.to_string());
+
+                        out.push('[');
+                        write_inline(out, child, page_url);
+                        out.push_str(&format!("]({href})"));
+                    }
+                    _ => write_inline(out, child, page_url),
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<html><body><main>
+<article class="day-desc"><h2>--- Day 1: Calorie Counting ---</h2><p>The jungle must be too overgrown to navigate in <a href="/2022/about">vehicles</a>.</p>
+<p>For example, suppose the Elves finish writing their items' <em>Calories</em>:</p>
+<pre><code>1000
+2000
+
+3000
+</code></pre>
+<ul>
+<li>The first Elf is carrying <code>6000</code> Calories.</li>
+<li>The second Elf is carrying <code><em>4000</em></code> Calories.</li>
+</ul>
+</article>
+<p>Your puzzle answer was <code>72602</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>Find the top <em>three</em> Elves.</p>
+</article>
+</main></body></html>"#;
+
+    #[test]
+    fn should_convert_articles_to_markdown() {
+        let page_url: Url = "https://adventofcode.com/2022/day/1".parse().unwrap();
+
+        let result = to_markdown(PAGE, &page_url);
+
+        let expected = "## Day 1: Calorie Counting
+
+The jungle must be too overgrown to navigate in [vehicles](https://adventofcode.com/2022/about).
+
+For example, suppose the Elves finish writing their items' *Calories*:
+
+```
+1000
+2000
+
+3000
+```
+
+- The first Elf is carrying `6000` Calories.
+- The second Elf is carrying `4000` Calories.
+
+## Part Two
+
+Find the top *three* Elves.
+";
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn should_extract_first_block_after_for_example() {
+        let page = r#"<article class="day-desc"><p>Given a map:</p>
+<pre><code>not the example</code></pre>
+<p>For example:</p>
+<pre><code>30373
+25512
+</code></pre>
+<pre><code>other</code></pre>
+</article>"#;
+
+        assert_eq!(
+            extract_example(page, None),
+            Some("30373\n25512\n".to_string())
+        );
+        assert_eq!(
+            extract_example(page, Some(0)),
+            Some("not the example".to_string())
+        );
+        assert_eq!(extract_example(page, Some(2)), Some("other".to_string()));
+        assert_eq!(extract_example(page, Some(3)), None);
+    }
+
+    #[test]
+    fn should_extract_example_from_fixture_page() {
+        let result = extract_example(PAGE, None);
+
+        assert_eq!(result, Some("1000\n2000\n\n3000\n".to_string()));
+    }
+
+    #[test]
+    fn should_only_contain_part1_when_locked() {
+        let page_url: Url = "https://adventofcode.com/2022/day/1".parse().unwrap();
+        let page = PAGE.split("<p>Your puzzle answer").next().unwrap();
+
+        let result = to_markdown(page, &page_url);
+
+        assert!(result.starts_with("## Day 1: Calorie Counting"));
+        assert!(!result.contains("Part Two"));
+        assert!(!is_complete(page));
+        assert!(is_complete(PAGE));
+    }
+}
@@ -0,0 +1,144 @@
+//! # Scaffold
+//!
+//! Module for creating new day crates from the calendar template
+
+use std::path::Path;
+
+use anyhow::Context;
+use tokio::fs;
+
+/// Name of the template crate inside the calendar directory
+const TEMPLATE_DIR: &str = "template";
+
+/// Directories in the template that should not be copied
+const IGNORED_DIRS: [&str; 1] = ["target"];
+
+/// Package name for a day crate, e.g. `cathode_ray_tube` for `cathode-ray_tube`
+pub fn package_name(name: &str) -> String {
+    name.replace('-', "_")
+}
+
+/// Copy `calendar/template` to `target` and rename its package after `name`
+pub async fn create_day_crate(calendar: &Path, target: &Path, name: &str) -> anyhow::Result<()> {
+    let template = calendar.join(TEMPLATE_DIR);
+
+    if fs::try_exists(target).await? {
+        anyhow::bail!("day crate already exists at `{}`", target.display());
+    }
+
+    copy_dir(&template, target).await.with_context(|| {
+        format!(
+            "failed to copy template from `{}` to `{}`",
+            template.display(),
+            target.display()
+        )
+    })?;
+
+    let manifest_path = target.join("Cargo.toml");
+    let manifest = fs::read_to_string(&manifest_path).await?;
+    fs::write(
+        &manifest_path,
+        rename_package(&manifest, &package_name(name)),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Replace the package `name` in a Cargo.toml manifest
+fn rename_package(manifest: &str, name: &str) -> String {
+    let mut in_package = false;
+    let mut renamed = false;
+
+    let lines: Vec<String> = manifest
+        .lines()
+        .map(|line| {
+            let trimmed = line.trim();
+
+            if trimmed.starts_with('[') {
+                in_package = trimmed == "[package]";
+            } else if in_package && !renamed && trimmed.starts_with("name") {
+                renamed = true;
+                return format!("name = \"{name}\"");
+            }
+
+            line.to_string()
+        })
+        .collect();
+
+    let mut manifest = lines.join("\n");
+    manifest.push('\n');
+    manifest
+}
+
+/// Recursively copy the contents of directory `from` into a new directory `to`
+async fn copy_dir(from: &Path, to: &Path) -> anyhow::Result<()> {
+    let mut pending = vec![(from.to_path_buf(), to.to_path_buf())];
+
+    while let Some((from, to)) = pending.pop() {
+        fs::create_dir_all(&to).await?;
+
+        let mut entries = fs::read_dir(&from).await?;
+
+        while let Some(entry) = entries.next_entry().await? {
+            let file_name = entry.file_name();
+
+            if entry.file_type().await?.is_dir() {
+                if !IGNORED_DIRS.iter().any(|dir| file_name == *dir) {
+                    pending.push((entry.path(), to.join(file_name)));
+                }
+            } else {
+                fs::copy(entry.path(), to.join(file_name)).await?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEMPLATE_MANIFEST: &str = "[package]
+name = \"template\"
+version = \"0.1.0\"
+edition = \"2021\"
+
+[dependencies]
+";
+
+    #[test]
+    fn should_rename_package() {
+        let result = rename_package(TEMPLATE_MANIFEST, "cathode_ray_tube");
+
+        assert_eq!(
+            result,
+            TEMPLATE_MANIFEST.replace("\"template\"", "\"cathode_ray_tube\"")
+        );
+    }
+
+    #[tokio::test]
+    async fn should_copy_template_into_new_day_crate() -> anyhow::Result<()> {
+        let calendar = tempfile::tempdir()?;
+        let template = calendar.path().join(TEMPLATE_DIR);
+
+        fs::create_dir_all(template.join("src")).await?;
+        fs::create_dir_all(template.join("target")).await?;
+        fs::write(template.join("Cargo.toml"), TEMPLATE_MANIFEST).await?;
+        fs::write(template.join("src/lib.rs"), "pub fn part1() {}\n").await?;
+
+        let result = calendar.path().join("16_proboscidea-volcanium");
+        create_day_crate(calendar.path(), &result, "proboscidea-volcanium").await?;
+
+        let manifest = fs::read_to_string(result.join("Cargo.toml")).await?;
+        assert!(manifest.contains("name = \"proboscidea_volcanium\""));
+        assert!(fs::try_exists(result.join("src/lib.rs")).await?);
+        assert!(!fs::try_exists(result.join("target")).await?);
+
+        let error = create_day_crate(calendar.path(), &result, "proboscidea-volcanium").await;
+        assert!(error.is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,92 @@
+//! # Status
+//!
+//! Module for reporting which days of the workspace have been solved, using
+//! the stars shown on the event calendar page
+
+use std::collections::BTreeMap;
+
+use scraper::{Html, Selector};
+
+use crate::{
+    config::OutdirPattern,
+    date::{Day, Year, DAYS},
+};
+
+/// Number of stars collected for each day, read from the calendar page
+///
+/// Days are marked with a `calendar-complete` class after the first star
+/// and `calendar-verycomplete` after the second one.
+pub fn parse_stars(html: &str) -> BTreeMap<Day, u8> {
+    let document = Html::parse_document(html);
+    let selector = Selector::parse("a[class*=\"calendar-day\"]").expect("selector should be valid");
+
+    document
+        .select(&selector)
+        .filter_map(|link| {
+            let classes: Vec<&str> = link.value().classes().collect();
+
+            let day = classes
+                .iter()
+                .find_map(|class| class.strip_prefix("calendar-day")?.parse().ok())?;
+
+            let stars = if classes.contains(&"calendar-verycomplete") {
+                2
+            } else if classes.contains(&"calendar-complete") {
+                1
+            } else {
+                0
+            };
+
+            Some((day, stars))
+        })
+        .collect()
+}
+
+/// Print the stars collected for every day directory matching `pattern`
+pub async fn print_status(
+    pattern: &OutdirPattern,
+    year: Year,
+    stars: &BTreeMap<Day, u8>,
+) -> anyhow::Result<()> {
+    let mut crates = 0;
+    let mut solved = 0;
+
+    for day in DAYS {
+        let Some(dir) = pattern.find(year, day).await? else {
+            continue;
+        };
+
+        let count = stars.get(&day).copied().unwrap_or_default();
+        let name = dir.file_name().unwrap_or_default().to_string_lossy();
+
+        crates += 1;
+        if count == 2 {
+            solved += 1;
+        }
+
+        println!("day {day:>2}  {:<2}  {name}", "*".repeat(count as usize));
+    }
+
+    println!("{solved} of {crates} day crates have both stars");
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_stars_from_calendar() {
+        let html = "<pre class=\"calendar\">\
+            <a aria-label=\"Day 1, two stars\" href=\"/2022/day/1\" class=\"calendar-day1 calendar-verycomplete\">1</a>\
+            <a aria-label=\"Day 2, one star\" href=\"/2022/day/2\" class=\"calendar-day2 calendar-complete\">2</a>\
+            <a aria-label=\"Day 3\" href=\"/2022/day/3\" class=\"calendar-day3\">3</a>\
+            <span class=\"calendar-day4\">4</span>\
+            </pre>";
+
+        let stars = parse_stars(html);
+
+        assert_eq!(stars, BTreeMap::from([(1, 2), (2, 1), (3, 0)]));
+    }
+}
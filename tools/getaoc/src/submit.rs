@@ -0,0 +1,318 @@
+//! # Submit
+//!
+//! Module for posting puzzle answers and interpreting the response
+
+use std::{
+    error,
+    fmt::{self, Display, Formatter},
+    path::Path,
+    str::FromStr,
+    time::Duration,
+};
+
+use clap::ValueEnum;
+use reqwest::Client;
+use scraper::{Html, Selector};
+use shared_lib::answers::Answers;
+use url::Url;
+
+use crate::{
+    date::{Day, Year},
+    error::{read_response, Error},
+};
+
+/// Puzzle part an answer is submitted for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Part {
+    #[value(name = "1")]
+    One,
+    #[value(name = "2")]
+    Two,
+}
+
+impl Part {
+    /// Value of the `level` form field expected by the answer endpoint
+    pub fn level(&self) -> &'static str {
+        match self {
+            Part::One => "1",
+            Part::Two => "2",
+        }
+    }
+
+    pub fn number(&self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.level())
+    }
+}
+
+/// Result of submitting an answer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong answer without a hint about which direction it is off by
+    Incorrect,
+    /// Answer was submitted too recently and has to wait before retrying
+    Wait(Duration),
+    /// Part was already solved, or is not unlocked yet
+    AlreadySolved,
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "That's the right answer!"),
+            Outcome::TooHigh => write!(f, "That's not the right answer; it is too high."),
+            Outcome::TooLow => write!(f, "That's not the right answer; it is too low."),
+            Outcome::Incorrect => write!(f, "That's not the right answer."),
+            Outcome::Wait(duration) => write!(
+                f,
+                "Answer submitted too recently; wait {}s before trying again.",
+                duration.as_secs()
+            ),
+            Outcome::AlreadySolved => write!(f, "This part has already been solved."),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ParseOutcomeError(String);
+
+impl Display for ParseOutcomeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "ParseOutcomeError: unrecognized answer response\n\n`{}`",
+            self.0
+        )
+    }
+}
+
+impl error::Error for ParseOutcomeError {}
+
+impl FromStr for Outcome {
+    type Err = ParseOutcomeError;
+
+    /// Parse the HTML page returned by the answer endpoint
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let text = article_text(s);
+
+        let outcome = if text.contains("That's the right answer") {
+            Outcome::Correct
+        } else if text.contains("your answer is too high") {
+            Outcome::TooHigh
+        } else if text.contains("your answer is too low") {
+            Outcome::TooLow
+        } else if text.contains("That's not the right answer") {
+            Outcome::Incorrect
+        } else if text.contains("You gave an answer too recently") {
+            Outcome::Wait(parse_wait_duration(&text).unwrap_or_default())
+        } else if text.contains("You don't seem to be solving the right level") {
+            Outcome::AlreadySolved
+        } else {
+            return Err(ParseOutcomeError(text));
+        };
+
+        Ok(outcome)
+    }
+}
+
+/// Collect the text content of the page's `<main><article>` element,
+/// falling back to the whole document when there is none
+fn article_text(html: &str) -> String {
+    let document = Html::parse_document(html);
+    let selector = Selector::parse("main article").expect("selector should be valid");
+
+    let text: String = match document.select(&selector).next() {
+        Some(article) => article.text().collect(),
+        None => document.root_element().text().collect(),
+    };
+
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Parse the remaining wait time from text like `You have 1m 30s left to wait.`
+fn parse_wait_duration(text: &str) -> Option<Duration> {
+    let (_, rest) = text.split_once("You have ")?;
+    let (amount, _) = rest.split_once(" left to wait")?;
+
+    amount
+        .split_whitespace()
+        .map(|part| {
+            let (value, unit) = part.split_at(part.len().checked_sub(1)?);
+            let value: u64 = value.parse().ok()?;
+
+            match unit {
+                "h" => Some(value * 60 * 60),
+                "m" => Some(value * 60),
+                "s" => Some(value),
+                _ => None,
+            }
+        })
+        .sum::<Option<u64>>()
+        .map(Duration::from_secs)
+}
+
+/// Post `answer` for `part` to the puzzle's answer url and parse the response
+pub async fn submit_answer(
+    client: &Client,
+    answer_url: Url,
+    part: Part,
+    answer: &str,
+) -> anyhow::Result<Outcome> {
+    let response = client
+        .post(answer_url)
+        .form(&[("level", part.level()), ("answer", answer)])
+        .send()
+        .await
+        .map_err(Error::from)?;
+    let html = read_response(response).await?;
+
+    Ok(html.parse()?)
+}
+
+/// Record a correct `answer` in the answer store at `path`
+///
+/// A store that belongs to another event year is left untouched. Returns
+/// whether the answer was recorded.
+pub fn record_answer(
+    path: &Path,
+    year: Year,
+    day: Day,
+    part: Part,
+    answer: &str,
+) -> anyhow::Result<bool> {
+    let mut answers = Answers::load(path)?;
+
+    if answers.year.is_some_and(|store_year| store_year != year) {
+        return Ok(false);
+    }
+
+    answers.year = Some(year);
+    answers.set(day, part.number(), answer)?;
+    answers.save(path)?;
+
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{create_client, url::build_answer_url};
+    use wiremock::{
+        matchers::{body_string, header, method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    fn page(message: &str) -> String {
+        format!("<html><body><main><article><p>{message}</p></article></main></body></html>")
+    }
+
+    #[test]
+    fn should_parse_correct() {
+        let result: Outcome = page("That's the right answer! You are one gold star closer.")
+            .parse()
+            .unwrap();
+
+        assert_eq!(result, Outcome::Correct);
+    }
+
+    #[test]
+    fn should_parse_too_high_and_too_low() {
+        let high: Outcome = page("That's not the right answer; your answer is too high.")
+            .parse()
+            .unwrap();
+        let low: Outcome = page("That's not the right answer; your answer is too low.")
+            .parse()
+            .unwrap();
+
+        assert_eq!(high, Outcome::TooHigh);
+        assert_eq!(low, Outcome::TooLow);
+    }
+
+    #[test]
+    fn should_parse_wait_duration() {
+        let result: Outcome = page(
+            "You gave an answer too recently; you have to wait after submitting an answer \
+            before trying again.  You have 1m 30s left to wait. [<a href=\"/2022/day/1\">Return to Day 1</a>]",
+        )
+        .parse()
+        .unwrap();
+
+        assert_eq!(result, Outcome::Wait(Duration::from_secs(90)));
+    }
+
+    #[test]
+    fn should_parse_already_solved() {
+        let result: Outcome =
+            page("You don't seem to be solving the right level.  Did you already complete it?")
+                .parse()
+                .unwrap();
+
+        assert_eq!(result, Outcome::AlreadySolved);
+    }
+
+    #[test]
+    fn should_fail_on_unrecognized_response() {
+        let result = page("Something unexpected").parse::<Outcome>();
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn should_record_answer_for_matching_year() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("answers.toml");
+
+        assert!(record_answer(&path, 2022, 1, Part::Two, "45000")?);
+        assert!(!record_answer(&path, 2021, 1, Part::One, "24000")?);
+
+        let answers = Answers::load(&path)?;
+        assert_eq!(answers.year, Some(2022));
+        assert_eq!(answers.get(1, 2), Some("45000"));
+        assert_eq!(answers.get(1, 1), None);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_post_answer_to_mock_server() -> anyhow::Result<()> {
+        let server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/2022/day/1/answer"))
+            .and(header("cookie", "session=abc123"))
+            .and(body_string("level=2&answer=45000"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(page("That's not the right answer; your answer is too low.")),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let base_url: Url = server.uri().parse()?;
+        let client = create_client("abc123", &base_url)?;
+
+        let result = submit_answer(
+            &client,
+            build_answer_url(&base_url, 2022, 1)?,
+            Part::Two,
+            "45000",
+        )
+        .await?;
+
+        assert_eq!(result, Outcome::TooLow);
+
+        Ok(())
+    }
+}
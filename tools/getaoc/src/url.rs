@@ -6,11 +6,33 @@ pub fn base_url() -> Result<Url, ParseError> {
     BASE_URL.parse::<Url>()
 }
 
-fn build_url(path: String) -> Result<Url, ParseError> {
-    let base_url = base_url()?;
+fn build_url(base_url: &Url, path: String) -> Result<Url, ParseError> {
     base_url.join(path.as_str())
 }
 
-pub fn build_input_url(year: u16, day: u8) -> Result<Url, ParseError> {
-    build_url(format!("{year}/day/{day}/input"))
+pub fn build_puzzle_url(base_url: &Url, year: u16, day: u8) -> Result<Url, ParseError> {
+    build_url(base_url, format!("{year}/day/{day}"))
+}
+
+pub fn build_input_url(base_url: &Url, year: u16, day: u8) -> Result<Url, ParseError> {
+    build_url(base_url, format!("{year}/day/{day}/input"))
+}
+
+pub fn build_answer_url(base_url: &Url, year: u16, day: u8) -> Result<Url, ParseError> {
+    build_url(base_url, format!("{year}/day/{day}/answer"))
+}
+
+pub fn build_calendar_url(base_url: &Url, year: u16) -> Result<Url, ParseError> {
+    build_url(base_url, format!("{year}"))
+}
+
+pub fn build_leaderboard_url(base_url: &Url, year: u16, id: u64) -> Result<Url, ParseError> {
+    build_url(
+        base_url,
+        format!("{year}/leaderboard/private/view/{id}.json"),
+    )
+}
+
+pub fn build_events_url(base_url: &Url) -> Result<Url, ParseError> {
+    build_url(base_url, "events".to_string())
 }
@@ -0,0 +1,194 @@
+//! # Whoami
+//!
+//! Module for checking which user a session belongs to and estimating when
+//! the session cookie expires
+
+use std::{
+    fmt::{self, Display, Formatter},
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, Duration, FixedOffset, Timelike};
+use scraper::{node::Node, Html, Selector};
+use tokio::fs;
+
+use crate::{cache::session_hash, date};
+
+/// Approximate lifetime of a session cookie after logging in
+pub const SESSION_LIFETIME_DAYS: i64 = 30;
+
+/// User logged in with a session
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct User {
+    /// Display name, like `(anonymous user #123456)` for anonymous users
+    pub name: String,
+    /// Total number of stars across all events
+    pub stars: Option<u32>,
+    /// Whether the user supports Advent of Code with AoC++
+    pub supporter: bool,
+    /// Time the session was first seen, filled in by
+    /// [`AocClient::whoami`](crate::AocClient::whoami)
+    pub first_seen: Option<DateTime<FixedOffset>>,
+}
+
+impl Display for User {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)?;
+
+        if let Some(stars) = self.stars {
+            write!(f, " ({stars}*)")?;
+        }
+
+        if self.supporter {
+            write!(f, " AoC++")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Parse the logged in user from the page header, which only shows a user
+/// when the session is valid
+pub fn parse_user(html: &str) -> Option<User> {
+    let document = Html::parse_document(html);
+    let selector = Selector::parse("header div.user").expect("selector should be valid");
+    let user = document.select(&selector).next()?;
+
+    let name = user.children().find_map(|node| match node.value() {
+        Node::Text(text) if !text.trim().is_empty() => Some(text.trim().to_string()),
+        _ => None,
+    })?;
+
+    let stars_selector = Selector::parse(".star-count").expect("selector should be valid");
+    let stars = user.select(&stars_selector).next().and_then(|stars| {
+        stars
+            .text()
+            .collect::<String>()
+            .trim()
+            .trim_end_matches('*')
+            .parse()
+            .ok()
+    });
+
+    let supporter_selector = Selector::parse(".supporter-badge").expect("selector should be valid");
+    let supporter = user.select(&supporter_selector).next().is_some();
+
+    Some(User {
+        name,
+        stars,
+        supporter,
+        first_seen: None,
+    })
+}
+
+/// File under `dir` that records when a session was first seen, named by
+/// [`session_hash`] so the file name does not reveal the session
+pub fn first_seen_path(dir: &Path, session: &str) -> PathBuf {
+    dir.join("sessions").join(session_hash(session))
+}
+
+/// Time the session was first seen, recording `now` if it is new
+pub async fn first_seen(
+    path: &Path,
+    now: DateTime<FixedOffset>,
+) -> anyhow::Result<DateTime<FixedOffset>> {
+    if let Ok(text) = fs::read_to_string(path).await {
+        if let Some(seen) = text.trim().parse().ok().and_then(date::from_timestamp) {
+            return Ok(seen);
+        }
+    }
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).await?;
+    }
+    fs::write(path, now.timestamp().to_string()).await?;
+
+    // Only whole seconds are stored, so return the same time as later reads
+    Ok(now.with_nanosecond(0).unwrap_or(now))
+}
+
+/// Estimated expiry time for a session first seen at `first_seen`
+pub fn estimated_expiry(first_seen: DateTime<FixedOffset>) -> DateTime<FixedOffset> {
+    first_seen + Duration::days(SESSION_LIFETIME_DAYS)
+}
+
+/// Describe when a session first seen at `first_seen` is likely to expire
+pub fn describe_expiry(first_seen: DateTime<FixedOffset>, now: DateTime<FixedOffset>) -> String {
+    let expiry = estimated_expiry(first_seen);
+    let days_left = (expiry - now).num_days();
+    let format = "%Y-%m-%d";
+
+    if days_left < 0 {
+        format!(
+            "session was first seen on {}, more than {SESSION_LIFETIME_DAYS} days ago, \
+            and may expire at any time",
+            first_seen.format(format)
+        )
+    } else {
+        format!(
+            "session was first seen on {} and will likely expire around {} (in {days_left} days)",
+            first_seen.format(format),
+            expiry.format(format)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(day: u32) -> DateTime<FixedOffset> {
+        FixedOffset::west_opt(5 * 60 * 60)
+            .unwrap()
+            .with_ymd_and_hms(2022, 12, day, 0, 0, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn should_parse_user_from_header() {
+        let html = "<header><div><h1 class=\"title-global\">Advent of Code</h1>\
+            <div class=\"user\">alice <a href=\"/2022/support\" class=\"supporter-badge\">AoC++</a> \
+            <span class=\"star-count\">44*</span></div></div></header>";
+
+        assert_eq!(
+            parse_user(html),
+            Some(User {
+                name: "alice".to_string(),
+                stars: Some(44),
+                supporter: true,
+                first_seen: None,
+            })
+        );
+    }
+
+    #[test]
+    fn should_not_find_user_when_logged_out() {
+        let html = "<header><div><h1 class=\"title-global\">Advent of Code</h1>\
+            <nav><ul><li><a href=\"/2022/auth/login\">[Log In]</a></li></ul></nav></div></header>";
+
+        assert_eq!(parse_user(html), None);
+    }
+
+    #[tokio::test]
+    async fn should_remember_first_seen_time() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = first_seen_path(dir.path(), "abc123");
+
+        assert_eq!(first_seen(&path, at(1)).await?, at(1));
+        assert_eq!(first_seen(&path, at(5)).await?, at(1));
+        assert_ne!(path, first_seen_path(dir.path(), "def456"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_describe_expiry() {
+        assert_eq!(
+            describe_expiry(at(1), at(11)),
+            "session was first seen on 2022-12-01 and will likely expire around \
+            2022-12-31 (in 20 days)"
+        );
+        assert!(describe_expiry(at(1) - Duration::days(40), at(11)).contains("at any time"));
+    }
+}